@@ -0,0 +1,154 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::util::write_atomic;
+
+const CACHE_FILE: &str = "binary_analysis_cache.json";
+/// 형식이 바뀌면 올린다. 버전이 다른 캐시는 버리고 다시 분석한다.
+const SCHEMA_VERSION: u32 = 2;
+/// 보관할 빌드 수. 가장 오래 쓰지 않은 빌드부터 지운다.
+const MAX_BUILDS: usize = 16;
+/// 통합 전의 캐시 파일. 열 때 지운다.
+const LEGACY_FILES: &[&str] = &["win_gworld_scan_cache.json", "mac_gworld_symbol_cache.json"];
+
+pub const GWORLD: &str = "GWorld";
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    /// 최근 사용순
+    builds: Vec<BuildEntry>,
+}
+
+impl Default for CacheFile {
+    fn default() -> Self {
+        Self { version: SCHEMA_VERSION, builds: Vec::new() }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct BuildEntry {
+    #[serde(flatten)]
+    key: BuildKey,
+    symbols: Vec<ResolvedSymbol>,
+}
+
+/// 실행 파일 + 빌드 식별자 (PE: TimeDateStamp + SizeOfImage, Mach-O: UUID)
+/// PE의 exe_path는 경로 형식(Windows/Wine)과 대소문자 차이를 없애기 위해 소문자 파일 이름만 쓴다.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BuildKey {
+    pub exe_path: String,
+    pub build_id: String,
+}
+
+/// 분석으로 찾은 주소. PE는 RVA, Mach-O는 파일상의 가상 주소
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ResolvedSymbol {
+    pub name: String,
+    pub address: u64,
+    /// 찾은 방법 (일치한 시그니처 이름 등)
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+/// WinProc/LinuxProc(.pdata 스캔)과 MacProc(심볼 테이블)이 함께 쓰는 바이너리 분석 캐시
+pub struct AnalysisCache {
+    path: PathBuf,
+    file: CacheFile,
+}
+
+impl AnalysisCache {
+    pub fn open(cache_dir: &Path) -> Self {
+        remove_legacy_files(cache_dir);
+        let path = cache_dir.join(CACHE_FILE);
+        let file = match fs::read_to_string(&path).map(|s| serde_json::from_str::<CacheFile>(&s)) {
+            Ok(Ok(file)) if file.version == SCHEMA_VERSION => file,
+            Ok(Ok(file)) => {
+                log::info!("분석 캐시 버전 불일치 ({} → {}), 새로 만듭니다.", file.version, SCHEMA_VERSION);
+                CacheFile::default()
+            }
+            Ok(Err(e)) => {
+                log::warn!("분석 캐시가 손상되어 새로 만듭니다: {}", e);
+                CacheFile::default()
+            }
+            Err(_) => CacheFile::default(),
+        };
+        Self { path, file }
+    }
+
+    /// 찾으면 그 빌드를 최근 사용으로 올린다.
+    pub fn lookup(&mut self, key: &BuildKey, name: &str) -> Option<ResolvedSymbol> {
+        let index = self.file.builds.iter().position(|b| b.key == *key)?;
+        let symbol = self.file.builds[index].symbols.iter().find(|s| s.name == name)?.clone();
+        if index > 0 {
+            let entry = self.file.builds.remove(index);
+            self.file.builds.insert(0, entry);
+            self.save();
+        }
+        Some(symbol)
+    }
+
+    /// 같은 실행 파일의 다른 빌드 기록은 지운다. (게임 업데이트로 더 이상 쓰이지 않음)
+    pub fn insert(&mut self, key: &BuildKey, symbol: ResolvedSymbol) {
+        self.file
+            .builds
+            .retain(|b| b.key.exe_path != key.exe_path || b.key.build_id == key.build_id);
+        let entry = match self.file.builds.iter().position(|b| b.key == *key) {
+            Some(index) => self.file.builds.remove(index),
+            None => BuildEntry { key: key.clone(), symbols: Vec::new() },
+        };
+        self.file.builds.insert(0, entry);
+        let symbols = &mut self.file.builds[0].symbols;
+        symbols.retain(|s| s.name != symbol.name);
+        symbols.push(symbol);
+        self.file.builds.truncate(MAX_BUILDS);
+        self.save();
+    }
+
+    /// 실행 파일의 모든 빌드에서 name 기록을 지운다. (오탐일 수 있는 결과를 버릴 때)
+    pub fn remove(&mut self, exe_path: &str, name: &str) {
+        let mut changed = false;
+        for build in self.file.builds.iter_mut().filter(|b| b.key.exe_path == exe_path) {
+            let before = build.symbols.len();
+            build.symbols.retain(|s| s.name != name);
+            changed |= build.symbols.len() != before;
+        }
+        if changed {
+            self.file.builds.retain(|b| !b.symbols.is_empty());
+            self.save();
+        }
+    }
+
+    fn save(&self) {
+        let result = serde_json::to_vec(&self.file)
+            .context("분석 캐시 직렬화 실패")
+            .and_then(|data| write_atomic(&self.path, &data));
+        if let Err(e) = result {
+            log::warn!("분석 캐시 저장 실패: {:#}", e);
+        }
+    }
+}
+
+/// 캐시 파일을 지운다. 다음 연결 때 다시 분석한다.
+pub fn clear(cache_dir: &Path) -> Result<()> {
+    remove_legacy_files(cache_dir);
+    match fs::remove_file(cache_dir.join(CACHE_FILE)) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e).context("분석 캐시 삭제 실패"),
+        _ => {
+            log::info!("분석 캐시를 삭제했습니다.");
+            Ok(())
+        }
+    }
+}
+
+fn remove_legacy_files(cache_dir: &Path) {
+    for name in LEGACY_FILES {
+        let path = cache_dir.join(name);
+        if fs::remove_file(&path).is_ok() {
+            log::info!("이전 형식의 분석 캐시 삭제: {}", path.display());
+        }
+    }
+}
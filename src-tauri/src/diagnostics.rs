@@ -0,0 +1,176 @@
+use serde::Serialize;
+
+use crate::offsets::{ChainValue, WuwaOffset};
+use crate::process_backend::{
+    ChainTrace, ProcessBackend, ResolvedPlayer, classify_ptr, read_resolved_player, resolve_chain_traced,
+};
+use crate::types::{PageInfo, PlayerInfo, PtrClass, TrackerError, hex_addr};
+
+/// 모든 variant의 포인터 체인을 한 번씩 따라가며 단계별 주소/값을 기록한 리포트.
+/// 사용자가 그대로 복사해 보낼 수 있도록 JSON으로 직렬화한다.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainReport {
+    pub game_build: Option<String>,
+    #[serde(serialize_with = "hex_addr::serialize")]
+    pub module_base: u64,
+    pub active_offset: Option<String>,
+    pub variants: Vec<VariantReport>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariantReport {
+    pub name: String,
+    pub game_versions: Vec<String>,
+    pub gworld: GWorldReport,
+    pub transform: Vec<StepReport>,
+    pub world_origin: Vec<StepReport>,
+    pub player: Option<PlayerInfo>,
+    pub error: Option<TrackerError>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GWorldSource {
+    /// 스캔(macOS: 심볼 또는 시그니처)으로 찾은 RVA
+    Scan,
+    /// 오프셋의 global_gworld
+    Fallback,
+}
+
+/// step.pointer는 읽은 UWorld 포인터의 분류 (읽지 못했으면 GWorld 슬롯 주소의 분류)
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GWorldReport {
+    pub source: GWorldSource,
+    #[serde(serialize_with = "hex_addr::serialize")]
+    pub rva: u64,
+    #[serde(flatten)]
+    pub step: StepReport,
+}
+
+/// Deref 한 단계. pointer는 역참조한 base 포인터의 classify_ptr 결과
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepReport {
+    pub name: String,
+    #[serde(serialize_with = "hex_addr::serialize")]
+    pub address: u64,
+    #[serde(serialize_with = "hex_addr::serialize_opt")]
+    pub value: Option<u64>,
+    pub pointer: PtrClass,
+    pub page: Option<PageInfo>,
+    /// address가 속한 영역에 매핑된 모듈/파일
+    pub module: Option<String>,
+}
+
+impl StepReport {
+    /// 영역의 모듈 이름 조회(GetMappedFileNameW 등)는 읽기 실패 경로에서 하지 않고 리포트를 만들 때만 한다.
+    fn new<B: ProcessBackend>(backend: &B, name: String, address: u64, value: Option<u64>, pointer: PtrClass) -> Self {
+        let region = backend.query_region(address);
+        StepReport {
+            name,
+            address,
+            value,
+            pointer,
+            page: region.as_ref().map(|r| r.page_info()),
+            module: region.and_then(|r| r.module),
+        }
+    }
+
+    fn from_trace<B: ProcessBackend>(backend: &B, trace: ChainTrace) -> Self {
+        let address = trace.base.wrapping_add(trace.field_offset);
+        Self::new(backend, trace.name, address, trace.value, classify_ptr(trace.base))
+    }
+}
+
+pub fn diagnose_player_chains<B: ProcessBackend>(
+    backend: &B,
+    offsets: &[WuwaOffset],
+    active_offset: Option<String>,
+) -> ChainReport {
+    ChainReport {
+        game_build: backend.game_build().map(|b| b.to_string()),
+        module_base: backend.module_base(),
+        active_offset,
+        variants: offsets.iter().map(|offset| diagnose_variant(backend, offset)).collect(),
+    }
+}
+
+/// read_player_info와 같은 순서로 읽되, 실패해도 다른 체인(world_origin)은 끝까지 기록한다.
+fn diagnose_variant<B: ProcessBackend>(backend: &B, offset: &WuwaOffset) -> VariantReport {
+    let module_base = backend.module_base();
+    let (source, rva) = match backend.gworld_rva() {
+        Some(rva) => (GWorldSource::Scan, rva),
+        None => (GWorldSource::Fallback, offset.global_gworld),
+    };
+    let gworld_result = backend.read_gworld(offset);
+    let gworld_addr = module_base.wrapping_add(rva);
+    let gworld_value = gworld_result.as_ref().ok().copied();
+    let gworld = GWorldReport {
+        source,
+        rva,
+        step: StepReport::new(
+            backend,
+            "GWorld".to_string(),
+            gworld_addr,
+            gworld_value,
+            classify_ptr(gworld_value.unwrap_or(gworld_addr)),
+        ),
+    };
+
+    let chain = offset.player_chain();
+    let mut transform = Vec::new();
+    let mut world_origin = Vec::new();
+    let result = gworld_result.and_then(|gworld| {
+        let transform_addr =
+            resolve_chain_traced(backend, gworld, &chain.transform, ChainValue::Transform, Some(&mut transform));
+        let world_origin_addr = resolve_chain_traced(
+            backend,
+            gworld,
+            &chain.world_origin,
+            ChainValue::IntVector,
+            Some(&mut world_origin),
+        );
+        let resolved =
+            ResolvedPlayer::new(transform_addr?, world_origin_addr?, &world_origin, offset.transform_layout());
+        read_resolved_player(backend, &resolved)
+    });
+
+    let (player, error) = match result {
+        Ok(player) => (Some(player), None),
+        Err(e) => (None, Some(TrackerError::from(&e))),
+    };
+    VariantReport {
+        name: offset.name.clone(),
+        game_versions: offset.game_versions.clone(),
+        gworld,
+        transform: transform.into_iter().map(|t| StepReport::from_trace(backend, t)).collect(),
+        world_origin: world_origin.into_iter().map(|t| StepReport::from_trace(backend, t)).collect(),
+        player,
+        error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot_proc::{MemorySnapshot, SnapshotProc, SnapshotRegion};
+
+    #[test]
+    fn gworld_step_classifies_the_value_read() {
+        let module_base = 0x7FF6_0000_0000;
+        let proc = SnapshotProc::new(MemorySnapshot {
+            module_base,
+            gworld_rva: Some(0x100),
+            regions: vec![SnapshotRegion { address: module_base + 0x100, bytes: 0x1_0000_0008u64.to_le_bytes().to_vec() }],
+        });
+        let offset: WuwaOffset = serde_json::from_str(r#"{"name":"v","global_gworld":0}"#).unwrap();
+        let report = diagnose_player_chains(&proc, &[offset], None);
+
+        let gworld = &report.variants[0].gworld.step;
+        assert_eq!(gworld.value, Some(0x1_0000_0008));
+        assert_eq!(gworld.pointer, PtrClass::Misaligned);
+    }
+}
@@ -34,6 +34,7 @@ pub fn launch_and_create_proc(
     path: &str,
     cache_dir: PathBuf,
     scan_config: Option<crate::offsets::GWorldScanConfig>,
+    variants: Vec<crate::offsets::WuwaOffset>,
 ) -> Result<crate::win_proc::WinProc> {
     use std::os::windows::io::AsRawHandle;
     use winapi::shared::minwindef::{DWORD, FALSE};
@@ -77,7 +78,7 @@ pub fn launch_and_create_proc(
     }
 
     log::info!("게임 실행됨, PID: {}, 핸들 복제 완료. 모듈 로드 대기 중...", pid);
-    crate::win_proc::WinProc::from_handle(dup_handle, pid, cache_dir, scan_config)
+    crate::win_proc::WinProc::from_handle(dup_handle, pid, cache_dir, scan_config, &variants)
 }
 
 #[cfg(target_os = "macos")]
@@ -0,0 +1,70 @@
+use std::fmt;
+
+use serde::Serialize;
+
+/// 실행 중인 게임 빌드 식별 정보.
+/// 오프셋 variant의 game_versions 태그는 build_id 또는 version(접두 일치)과 비교한다.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct GameBuild {
+    /// PE: "pe:{TimeDateStamp:08X}-{SizeOfImage:X}", Mach-O: "macho:{UUID}"
+    pub build_id: String,
+    /// 실행 파일에 기록된 버전 문자열 (e.g. "2.4.1.0"). 없으면 None
+    pub version: Option<String>,
+}
+
+impl GameBuild {
+    pub fn from_pe(timestamp: u32, size_of_image: u32, version: Option<String>) -> Self {
+        Self {
+            build_id: format!("pe:{:08X}-{:X}", timestamp, size_of_image),
+            version,
+        }
+    }
+
+    pub fn from_macho_uuid(uuid: &str) -> Self {
+        Self {
+            build_id: format!("macho:{}", uuid.to_ascii_uppercase()),
+            version: None,
+        }
+    }
+
+    /// "2.4"는 "2.4.1.0"과 일치하지만 "2.40.0"과는 일치하지 않는다.
+    pub fn matches(&self, tag: &str) -> bool {
+        if tag.eq_ignore_ascii_case(&self.build_id) {
+            return true;
+        }
+        self.version.as_deref().is_some_and(|version| {
+            version == tag
+                || version
+                    .strip_prefix(tag)
+                    .is_some_and(|rest| rest.starts_with('.'))
+        })
+    }
+}
+
+impl fmt::Display for GameBuild {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.version {
+            Some(version) => write!(f, "{} ({})", version, self.build_id),
+            None => write!(f, "{}", self.build_id),
+        }
+    }
+}
+
+/// 디스크의 PE 파일에서 VERSIONINFO 리소스의 버전을 읽는다.
+/// 문자열 ProductVersion → FileVersion → 고정 FileVersion 순으로 사용한다.
+#[cfg(any(windows, target_os = "linux"))]
+pub fn pe_file_version(path: &std::path::Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    let pe = goblin::pe::PE::parse(&bytes).ok()?;
+    let info = pe.resource_data?.version_info?;
+    info.string_info
+        .product_version()
+        .or_else(|| info.string_info.file_version())
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .or_else(|| {
+            let fixed = info.fixed_info?;
+            let v = fixed.file_version();
+            Some(format!("{}.{}.{}.{}", v.major, v.minor, v.build, v.revision))
+        })
+}
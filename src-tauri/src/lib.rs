@@ -1,16 +1,31 @@
+#[cfg(target_os = "linux")]
+mod linux_proc;
 #[cfg(target_os = "macos")]
 mod mac_proc;
+mod analysis_cache;
+mod diagnostics;
 mod game_launcher;
+mod game_version;
+mod macho_scan;
+mod name_resolver;
 mod native_collector;
+mod offset_history;
 mod offset_manager;
 mod offsets;
+#[cfg(any(windows, target_os = "linux"))]
+mod pe_image;
+#[cfg(any(windows, target_os = "linux"))]
+mod pe_scan;
 mod peer_manager;
 mod process_backend;
 mod room_code_generator;
 mod rtc_supervisor;
+mod sig_scan;
 mod signaling_handler;
+mod snapshot_proc;
 mod types;
 mod util;
+mod variant_stats;
 #[cfg(windows)]
 mod win_proc;
 
@@ -27,12 +42,12 @@ use tauri::{
 use tauri_plugin_notification::NotificationExt;
 use tokio::sync::{Mutex, mpsc, oneshot, watch};
 use tokio_util::sync::CancellationToken;
-use types::{GlobalState, LocalStorageConfig};
+use types::{GlobalState, LocalStorageConfig, PlayerInfo};
 use util::get_config;
 #[cfg(all(feature = "store", windows))]
 use windows::core::AgileReference;
 
-#[cfg(windows)]
+#[cfg(any(windows, target_os = "linux"))]
 const GAME_PROCESS_NAME: &str = "Client-Win64-Shipping.exe";
 #[cfg(target_os = "macos")]
 const GAME_PROCESS_NAME: &str = "Client-Mac-Shipping";
@@ -40,7 +55,8 @@ const GAME_PROCESS_NAME: &str = "Client-Mac-Shipping";
 struct TauriState {
     supervisor_tx: mpsc::Sender<SupervisorCommand>,
     global_state: watch::Sender<GlobalState>,
-    offsets: Arc<Mutex<Option<TrackerConfig>>>,
+    offsets: Arc<Mutex<Option<Arc<TrackerConfig>>>>,
+    offset_sources: Arc<offset_manager::OffsetSources>,
 }
 
 #[tauri::command]
@@ -78,6 +94,8 @@ async fn write_config(
     start_in_tray: Option<bool>,
     game_path: Option<String>,
 ) -> Result<(), String> {
+    // 이 커맨드가 다루지 않는 고급 설정은 기존 값을 유지한다.
+    let existing = get_config(app_handle.clone()).await.unwrap_or_default();
     let Ok(_) = util::write_config(
         app_handle,
         LocalStorageConfig {
@@ -87,6 +105,7 @@ async fn write_config(
             auto_attach_enabled,
             start_in_tray,
             game_path,
+            ..existing
         },
     )
     .await
@@ -96,6 +115,58 @@ async fn write_config(
     Ok(())
 }
 
+#[tauri::command]
+async fn set_max_sample_rate(app_handle: AppHandle, max_sample_rate: Option<u32>) -> Result<(), String> {
+    let config = get_config(app_handle.clone()).await.unwrap_or_default();
+    util::write_config(
+        app_handle.clone(),
+        LocalStorageConfig { max_sample_rate, ..config },
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    app_handle
+        .state::<TauriState>()
+        .supervisor_tx
+        .send(SupervisorCommand::SetMaxSampleRate(max_sample_rate))
+        .await
+        .map_err(|e| format!("앱 내부 오류: {}", e))
+}
+
+/// 오프셋 소스 목록과 오프라인 모드를 저장하고 바로 다시 가져온다.
+#[tauri::command]
+async fn set_offset_sources(
+    app_handle: AppHandle,
+    offset_sources: Option<Vec<String>>,
+    offline_mode: Option<bool>,
+) -> Result<(), String> {
+    if let Some(sources) = &offset_sources {
+        offset_manager::validate_sources(sources).map_err(|e| format!("{:#}", e))?;
+    }
+    let config = get_config(app_handle.clone()).await.unwrap_or_default();
+    util::write_config(
+        app_handle.clone(),
+        LocalStorageConfig { offset_sources, offline_mode, ..config },
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    app_handle.state::<TauriState>().offset_sources.request_refresh();
+    Ok(())
+}
+
+/// 소스별 마지막 오프셋 가져오기 결과 (문제 해결용)
+#[tauri::command]
+async fn get_offset_source_status(app_handle: AppHandle) -> offset_manager::OffsetSourceStatus {
+    app_handle
+        .state::<TauriState>()
+        .offset_sources
+        .status
+        .lock()
+        .await
+        .clone()
+}
+
 #[tauri::command]
 async fn scan_game_candidates() -> Vec<String> {
     tokio::task::spawn_blocking(game_launcher::scan_game_candidates)
@@ -155,6 +226,156 @@ async fn restart_external_signaling_client(app_handle: AppHandle) -> Result<Stri
     }
 }
 
+#[tauri::command]
+async fn save_memory_snapshot(app_handle: AppHandle) -> Result<String, String> {
+    let (resp_tx, resp_rx) = oneshot::channel();
+    app_handle
+        .state::<TauriState>()
+        .supervisor_tx
+        .send(SupervisorCommand::RecordSnapshot(resp_tx))
+        .await
+        .map_err(|e| format!("앱 내부 오류: {}", e))?;
+
+    match resp_rx.await {
+        Ok(Ok(path)) => Ok(path),
+        Ok(Err(e)) => Err(e),
+        Err(e) => Err(format!("앱 내부 오류: {}", e)),
+    }
+}
+
+/// 모든 오프셋 variant의 포인터 체인을 단계별로 기록한 JSON 리포트 (로그에도 남긴다)
+#[tauri::command]
+async fn diagnose_pointer_chains(app_handle: AppHandle) -> Result<String, String> {
+    let (resp_tx, resp_rx) = oneshot::channel();
+    app_handle
+        .state::<TauriState>()
+        .supervisor_tx
+        .send(SupervisorCommand::DiagnoseChains(resp_tx))
+        .await
+        .map_err(|e| format!("앱 내부 오류: {}", e))?;
+
+    match resp_rx.await {
+        Ok(Ok(report)) => Ok(report),
+        Ok(Err(e)) => Err(e),
+        Err(e) => Err(format!("앱 내부 오류: {}", e)),
+    }
+}
+
+#[tauri::command]
+async fn replay_memory_snapshot(app_handle: AppHandle, path: String) -> Result<PlayerInfo, String> {
+    let variants = app_handle
+        .state::<TauriState>()
+        .offsets
+        .lock()
+        .await
+        .as_ref()
+        .map(|c| c.offsets.clone())
+        .ok_or_else(|| "오프셋 데이터를 불러오는 중입니다...".to_string())?;
+
+    tokio::task::spawn_blocking(move || {
+        native_collector::replay_snapshot(std::path::Path::new(&path), &variants)
+    })
+    .await
+    .map_err(|e| format!("태스크 실패: {}", e))?
+}
+
+/// 디스크의 게임 실행 파일에서 GWorld RVA를 구한다. (오프셋 관리용)
+/// seed_cache가 true면 결과를 GWorld 스캔 캐시에 미리 넣어 다음 연결 시 스캔을 생략한다.
+#[tauri::command]
+async fn scan_game_exe(app_handle: AppHandle, path: String, seed_cache: bool) -> Result<serde_json::Value, String> {
+    #[cfg(any(windows, target_os = "linux"))]
+    {
+        let scan_config = app_handle
+            .state::<TauriState>()
+            .offsets
+            .lock()
+            .await
+            .as_ref()
+            .map(|c| c.gworld_scan.clone())
+            .unwrap_or_default();
+        let cache_dir = app_handle.path().app_config_dir().map_err(|e| e.to_string())?;
+
+        tokio::task::spawn_blocking(move || {
+            let entry = pe_scan::scan_exe_file(std::path::Path::new(&path), &scan_config)
+                .map_err(|e| e.to_string())?;
+            if seed_cache {
+                pe_scan::seed_cache(&cache_dir, &entry);
+            }
+            serde_json::to_value(&entry).map_err(|e| e.to_string())
+        })
+        .await
+        .map_err(|e| format!("태스크 실패: {}", e))?
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let _ = (app_handle, path, seed_cache);
+        Err("macOS 빌드는 PE 실행 파일 스캔을 지원하지 않습니다.".to_string())
+    }
+}
+
+/// GWorld 스캔/심볼 분석 캐시를 지운다. 다음 연결 때 다시 분석한다.
+#[tauri::command]
+async fn clear_analysis_cache(app_handle: AppHandle) -> Result<(), String> {
+    let cache_dir = app_handle.path().app_config_dir().map_err(|e| e.to_string())?;
+    analysis_cache::clear(&cache_dir).map_err(|e| format!("{:#}", e))
+}
+
+/// GUI 없이 실행하는 관리용 명령. 처리했으면 종료 코드를 반환한다.
+///   --scan-gworld <exe> [--config <tracker-offsets-v2.json>]
+/// Mach-O는 모든 플랫폼에서, PE는 Windows/Linux 빌드에서 스캔한다.
+pub fn run_cli(args: &[String]) -> Option<i32> {
+    let pos = args.iter().position(|a| a == "--scan-gworld")?;
+
+    let Some(exe) = args.get(pos + 1) else {
+        eprintln!("사용법: --scan-gworld <exe> [--config <tracker-offsets-v2.json>]");
+        return Some(2);
+    };
+    let config_path = args.iter().position(|a| a == "--config").and_then(|i| args.get(i + 1));
+    let scan_config = match config_path.map(|p| load_scan_config(p)) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            eprintln!("설정 파일을 읽지 못했습니다: {:#}", e);
+            return Some(2);
+        }
+        None => offsets::GWorldScanConfig::default(),
+    };
+
+    match scan_exe_json(std::path::Path::new(exe), &scan_config) {
+        Ok(json) => {
+            println!("{}", json);
+            Some(0)
+        }
+        Err(e) => {
+            eprintln!("GWorld 스캔 실패: {:#}", e);
+            Some(1)
+        }
+    }
+}
+
+fn scan_exe_json(path: &std::path::Path, scan_config: &offsets::GWorldScanConfig) -> anyhow::Result<String> {
+    if macho_scan::is_macho_file(path)? {
+        let result = macho_scan::scan_file(path, scan_config)?;
+        return Ok(serde_json::to_string_pretty(&result)?);
+    }
+
+    #[cfg(any(windows, target_os = "linux"))]
+    {
+        let entry = pe_scan::scan_exe_file(path, scan_config)?;
+        Ok(serde_json::to_string_pretty(&entry)?)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        anyhow::bail!("macOS 빌드는 PE 실행 파일 스캔을 지원하지 않습니다.")
+    }
+}
+
+fn load_scan_config(path: &str) -> anyhow::Result<offsets::GWorldScanConfig> {
+    let data = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str::<TrackerConfig>(&data)?.gworld_scan)
+}
+
 #[tauri::command]
 async fn channel_get_config(app_handle: AppHandle) -> Result<LocalStorageConfig, String> {
     get_config(app_handle).await.map_err(|e| e.to_string())
@@ -212,7 +433,13 @@ fn init_logger() {
     let log_dir = std::env::var_os("HOME")
         .map(|d| std::path::PathBuf::from(d).join("Library").join("Logs").join("com.wumadevs.wumatracker"));
 
-    #[cfg(not(any(windows, target_os = "macos")))]
+    #[cfg(target_os = "linux")]
+    let log_dir = std::env::var_os("XDG_STATE_HOME")
+        .map(std::path::PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|d| std::path::PathBuf::from(d).join(".local").join("state")))
+        .map(|d| d.join("com.wumadevs.wumatracker").join("logs"));
+
+    #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
     let log_dir: Option<std::path::PathBuf> = None;
 
     let file_spec = if let Some(dir) = log_dir {
@@ -246,6 +473,8 @@ pub async fn run() {
     let offsets_shared = Arc::new(Mutex::new(None));
     let offsets_for_setup = offsets_shared.clone();
     let offsets_for_supervisor = offsets_shared.clone();
+    let offset_sources = Arc::new(offset_manager::OffsetSources::default());
+    let offset_sources_for_setup = offset_sources.clone();
 
     let mut builder = tauri::Builder::default().plugin(tauri_plugin_clipboard_manager::init());
     #[cfg(not(feature = "store"))]
@@ -273,6 +502,7 @@ pub async fn run() {
             supervisor_tx,
             global_state: global_state_tx,
             offsets: offsets_shared,
+            offset_sources,
         })
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_process::init())
@@ -291,7 +521,8 @@ pub async fn run() {
 
             let handle = app.handle().clone();
             tokio::spawn(async move {
-                offset_manager::start_offset_loading(handle, offsets_for_setup).await;
+                offset_manager::start_offset_loading(handle, offsets_for_setup, offset_sources_for_setup)
+                    .await;
             });
 
             let quit_menu = MenuItem::with_id(app, "quit", "종료", true, None::<&str>)?;
@@ -395,6 +626,14 @@ pub async fn run() {
             is_store_build,
             scan_game_candidates,
             launch_and_attach,
+            save_memory_snapshot,
+            diagnose_pointer_chains,
+            replay_memory_snapshot,
+            set_max_sample_rate,
+            scan_game_exe,
+            clear_analysis_cache,
+            set_offset_sources,
+            get_offset_source_status,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application");
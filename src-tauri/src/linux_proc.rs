@@ -0,0 +1,397 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::analysis_cache::BuildKey;
+use crate::game_version::GameBuild;
+use crate::offsets::{GWorldScanConfig, WuwaOffset};
+use crate::pe_scan;
+use crate::process_backend::{ProcessBackend, classify_ptr, read_each};
+use crate::types::{MemoryRegion, NativeError, PageState, Protection, ReadFailure, ReadFault};
+use anyhow::{Context, Result, bail};
+
+// /proc/<pid>/comm 은 TASK_COMM_LEN(16) - 1 바이트로 잘린다.
+const COMM_MAX_LEN: usize = 15;
+// process_vm_readv 한 번에 넘길 수 있는 iovec 수 (UIO_MAXIOV)
+const IOV_MAX: usize = 1024;
+
+/// Wine/Proton 위에서 실행 중인 Windows 게임 프로세스.
+/// PE 이미지는 Wine이 파일 매핑으로 올리므로 /proc/<pid>/maps에서 base를 찾고,
+/// 메모리는 process_vm_readv로 읽는다. (같은 uid + ptrace 권한 필요)
+pub struct LinuxProc {
+    pid: libc::pid_t,
+    base_addr: u64,
+    exe_path: String,
+    gworld_rva: u64,
+    cache_dir: PathBuf,
+    scan_config: GWorldScanConfig,
+    game_build: Option<GameBuild>,
+}
+
+impl LinuxProc {
+    pub fn new(
+        name: &str,
+        cache_dir: PathBuf,
+        scan_config: Option<GWorldScanConfig>,
+        variants: &[WuwaOffset],
+    ) -> Result<Self> {
+        let scan_config = scan_config.unwrap_or_default();
+
+        let candidates = Self::find_pids_by_name(name);
+        if candidates.is_empty() {
+            bail!("게임이 실행 중이 아닙니다.");
+        }
+
+        // Proton 래퍼 등 같은 이름을 가진 프로세스가 여럿일 수 있으므로
+        // 실제로 PE 이미지가 매핑된 프로세스를 고른다.
+        let (pid, base_addr, exe_path) = candidates
+            .into_iter()
+            .find_map(|pid| {
+                Self::find_image_base(pid, name).map(|(base, path)| (pid, base, path))
+            })
+            .with_context(|| "게임 Base 주소를 가져오지 못했습니다. (/proc/<pid>/maps에 PE 이미지 없음)".to_string())?;
+
+        let mut proc = LinuxProc { pid, base_addr, exe_path, gworld_rva: 0, cache_dir, scan_config, game_build: None };
+
+        let mut mz = [0u8; 2];
+        if let Err(e) = proc.read_bytes(base_addr, &mut mz) {
+            bail!(
+                "게임에 연결하지 못했습니다. ptrace 권한(kernel.yama.ptrace_scope)을 확인하세요. {}",
+                e
+            );
+        }
+        if &mz != b"MZ" {
+            bail!("게임 Base 주소({:X})에서 PE 헤더를 찾지 못했습니다.", base_addr);
+        }
+
+        proc.game_build = pe_scan::game_build(&proc, base_addr, &proc.exe_path);
+        proc.gworld_rva = pe_scan::initial_gworld_rva(
+            &proc, base_addr, &proc.exe_path, &proc.cache_dir, &proc.scan_config, variants,
+        );
+
+        log::info!(
+            "Process '{}' connected (Wine)! PID: {}, Base: {:X}, Build: {}, GWorld RVA: {}",
+            name, pid, base_addr,
+            proc.game_build.as_ref().map(|b| b.to_string()).unwrap_or_else(|| "?".to_string()),
+            if proc.gworld_rva != 0 { format!("{:X}", proc.gworld_rva) } else { "폴백".to_string() }
+        );
+
+        Ok(proc)
+    }
+
+    /// /proc/*/cmdline 의 argv[0] 또는 comm 으로 Wine 게임 프로세스를 찾는다.
+    /// Wine은 argv[0]을 "C:\\...\\Client-Win64-Shipping.exe" 형태의 Windows 경로로 바꿔 둔다.
+    fn find_pids_by_name(name: &str) -> Vec<libc::pid_t> {
+        let Ok(entries) = fs::read_dir("/proc") else {
+            return vec![];
+        };
+
+        let mut pids: Vec<libc::pid_t> = entries
+            .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+            .filter(|pid: &libc::pid_t| Self::process_matches(*pid, name))
+            .collect();
+        pids.sort_unstable();
+        pids
+    }
+
+    fn process_matches(pid: libc::pid_t, name: &str) -> bool {
+        if fs::read(format!("/proc/{}/cmdline", pid)).is_ok_and(|cmdline| arg0_matches(&cmdline, name)) {
+            return true;
+        }
+        fs::read(format!("/proc/{}/comm", pid)).is_ok_and(|comm| comm_matches(&comm, name))
+    }
+
+    /// offset 0으로 매핑된 게임 실행 파일 영역 중 가장 낮은 주소를 image base로 본다.
+    fn find_image_base(pid: libc::pid_t, name: &str) -> Option<(u64, String)> {
+        let maps = fs::read_to_string(format!("/proc/{}/maps", pid)).ok()?;
+        maps.lines()
+            .filter_map(parse_maps_line)
+            .filter_map(|(region, offset)| {
+                let path = region.module?;
+                (offset == 0 && file_name_of(&path).eq_ignore_ascii_case(name)).then_some((region.base, path))
+            })
+            .min_by_key(|(start, _)| *start)
+    }
+}
+
+impl ProcessBackend for LinuxProc {
+    fn is_alive(&self) -> bool {
+        // stat: "pid (comm) S ..." — comm에 공백/괄호가 있을 수 있으므로 마지막 ')' 뒤를 본다.
+        fs::read_to_string(format!("/proc/{}/stat", self.pid))
+            .ok()
+            .and_then(|stat| {
+                let state = stat.rsplit_once(')')?.1.trim_start().chars().next()?;
+                Some(state != 'Z' && state != 'X')
+            })
+            .unwrap_or(false)
+    }
+
+    fn read_bytes(&self, address: u64, buffer: &mut [u8]) -> Result<(), NativeError> {
+        let local = libc::iovec {
+            iov_base: buffer.as_mut_ptr() as *mut libc::c_void,
+            iov_len: buffer.len(),
+        };
+        let remote = libc::iovec {
+            iov_base: address as *mut libc::c_void,
+            iov_len: buffer.len(),
+        };
+
+        let read = unsafe { libc::process_vm_readv(self.pid, &local, 1, &remote, 1, 0) };
+        if read >= 0 && read as usize == buffer.len() {
+            return Ok(());
+        }
+
+        let failure = ReadFailure::at(address);
+        let failure = if read < 0 {
+            let code = std::io::Error::last_os_error().raw_os_error().unwrap_or(-1);
+            let fault = match code {
+                libc::EFAULT => ReadFault::Partial,
+                libc::EPERM | libc::EACCES => ReadFault::AccessDenied,
+                libc::ESRCH => ReadFault::InvalidHandle,
+                _ => ReadFault::Other,
+            };
+            failure.fault(fault, Some(code))
+        } else {
+            failure
+                .fault(ReadFault::Partial, None)
+                .note(format!("{}/{} 바이트만 읽음", read, buffer.len()))
+        };
+        Err(NativeError::value_read(failure))
+    }
+
+    /// process_vm_readv 한 번으로 모든 요청을 읽는다. (IOV_MAX개씩)
+    /// 전부 읽지 못했으면 어느 요청이 실패했는지 알 수 있도록 하나씩 다시 읽는다.
+    fn read_many(&self, requests: &mut [(u64, &mut [u8])]) -> Result<(), (usize, NativeError)> {
+        let mut complete = true;
+        for chunk in requests.chunks_mut(IOV_MAX) {
+            let total: usize = chunk.iter().map(|(_, buffer)| buffer.len()).sum();
+            let (local, remote): (Vec<libc::iovec>, Vec<libc::iovec>) = chunk
+                .iter_mut()
+                .map(|(address, buffer)| {
+                    (
+                        libc::iovec { iov_base: buffer.as_mut_ptr() as *mut libc::c_void, iov_len: buffer.len() },
+                        libc::iovec { iov_base: *address as *mut libc::c_void, iov_len: buffer.len() },
+                    )
+                })
+                .unzip();
+            let read = unsafe {
+                libc::process_vm_readv(
+                    self.pid,
+                    local.as_ptr(),
+                    local.len() as libc::c_ulong,
+                    remote.as_ptr(),
+                    remote.len() as libc::c_ulong,
+                    0,
+                )
+            };
+            if read < 0 || read as usize != total {
+                complete = false;
+                break;
+            }
+        }
+        if complete {
+            return Ok(());
+        }
+        read_each(self, requests)
+    }
+
+    fn regions(&self) -> Vec<MemoryRegion> {
+        match fs::read_to_string(format!("/proc/{}/maps", self.pid)) {
+            Ok(maps) => maps.lines().filter_map(parse_maps_line).map(|(region, _)| region).collect(),
+            Err(e) => {
+                log::debug!("/proc/{}/maps 읽기 실패: {}", self.pid, e);
+                Vec::new()
+            }
+        }
+    }
+
+    fn gworld_ready(&self) -> bool {
+        self.gworld_rva != 0
+    }
+
+    fn module_base(&self) -> u64 {
+        self.base_addr
+    }
+
+    fn gworld_rva(&self) -> Option<u64> {
+        (self.gworld_rva != 0).then_some(self.gworld_rva)
+    }
+
+    fn game_build(&self) -> Option<&GameBuild> {
+        self.game_build.as_ref()
+    }
+
+    fn build_key(&self) -> Option<BuildKey> {
+        self.game_build.as_ref().map(|build| pe_scan::game_build_key(&self.exe_path, build))
+    }
+
+    fn active_offset_name(&self, offset: &WuwaOffset) -> String {
+        if self.gworld_rva != 0 {
+            format!("{:X}", self.gworld_rva)
+        } else {
+            format!("오프셋 {}", offset.name)
+        }
+    }
+
+    fn set_scan_config(&mut self, scan_config: GWorldScanConfig) -> bool {
+        if self.scan_config == scan_config {
+            return false;
+        }
+        self.scan_config = scan_config;
+        true
+    }
+
+    fn rescan_gworld(&mut self, variants: &[WuwaOffset]) {
+        if !self.scan_config.enabled {
+            log::info!("GWorld 재스캔 스킵 (원격 설정으로 비활성화됨)");
+            return;
+        }
+        match pe_scan::rescan_gworld_rva(
+            self, self.base_addr, &self.exe_path, &self.cache_dir, &self.scan_config, variants,
+        ) {
+            Ok(rva) => {
+                log::info!("GWorld 재스캔 성공: RVA 0x{:X}", rva);
+                self.gworld_rva = rva;
+            }
+            Err(e) => {
+                log::warn!("GWorld 재스캔 실패, 폴백 유지: {}", e);
+                self.gworld_rva = 0;
+            }
+        }
+    }
+
+    fn read_gworld(&self, offset: &WuwaOffset) -> Result<u64, NativeError> {
+        let (rva, source) = if self.gworld_rva != 0 {
+            (self.gworld_rva, "scan")
+        } else {
+            (offset.global_gworld, "fb")
+        };
+        let target = self.base_addr + rva;
+        self.read_memory::<u64>(target).map_err(|e| {
+            e.in_chain(&format!("gworld[{}]", source), self.base_addr, rva, classify_ptr(self.base_addr))
+        })
+    }
+}
+
+impl Drop for LinuxProc {
+    fn drop(&mut self) {
+        log::info!("Detaching from PID {}", self.pid);
+    }
+}
+
+/// cmdline의 argv[0]이 Unix 경로든 Wine이 바꿔 둔 Windows 경로든 파일 이름으로 비교한다.
+fn arg0_matches(cmdline: &[u8], name: &str) -> bool {
+    let arg0 = cmdline.split(|byte| *byte == 0).next().unwrap_or_default();
+    file_name_of(&String::from_utf8_lossy(arg0)).eq_ignore_ascii_case(name)
+}
+
+/// comm은 COMM_MAX_LEN 바이트로 잘려 있으므로 이름도 같은 길이로 잘라 비교한다.
+fn comm_matches(comm: &[u8], name: &str) -> bool {
+    let comm = comm.strip_suffix(b"\n").unwrap_or(comm);
+    let name = name.as_bytes();
+    !comm.is_empty() && comm == &name[..name.len().min(COMM_MAX_LEN)]
+}
+
+/// "start-end perms offset dev inode [path]" → (영역, 파일 오프셋)
+/// 권한이 없는(---p) 매핑은 Wine이 VirtualAlloc(MEM_RESERVE)으로 잡아 둔 공간이므로 Reserve로 본다.
+/// 경로 앞은 공백으로 채워져 있으므로, 경로는 다섯 필드 뒤의 나머지 전체로 본다. (경로 안의 공백 유지)
+fn parse_maps_line(line: &str) -> Option<(MemoryRegion, u64)> {
+    let mut rest = line.trim_end_matches('\n');
+    let mut next_field = || {
+        let trimmed = rest.trim_start();
+        let (value, tail) = trimmed.split_once(' ').unwrap_or((trimmed, ""));
+        rest = tail;
+        value
+    };
+    let (start, end) = next_field().split_once('-')?;
+    let start = u64::from_str_radix(start, 16).ok()?;
+    let end = u64::from_str_radix(end, 16).ok()?;
+    let perms = next_field().as_bytes();
+    let offset = u64::from_str_radix(next_field(), 16).ok()?;
+    let _dev = next_field();
+    let _inode = next_field();
+    // 매핑 뒤에 파일이 교체/삭제되면 " (deleted)"가 붙는다. (실행 중 게임 업데이트)
+    let path = rest.trim_start();
+    let path = path.strip_suffix(" (deleted)").unwrap_or(path);
+
+    let protection = Protection {
+        read: perms.first() == Some(&b'r'),
+        write: perms.get(1) == Some(&b'w'),
+        execute: perms.get(2) == Some(&b'x'),
+        guard: false,
+    };
+    let state = if protection.read || protection.write || protection.execute {
+        PageState::Commit
+    } else {
+        PageState::Reserve
+    };
+    // [heap], [stack] 등 의사 경로는 모듈이 아니다.
+    let module = path.starts_with('/').then(|| path.to_string());
+    let region = MemoryRegion { base: start, size: end.checked_sub(start)?, state, protection, module };
+    Some((region, offset))
+}
+
+/// Unix 경로와 Wine의 Windows 경로 모두에서 파일 이름만 꺼낸다.
+fn file_name_of(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_path_with_spaces() {
+        let line = "140000000-140001000 r--p 00000000 00:2a 1234                       /home/user/Games/Wuthering Waves/Client/Binaries/Win64/Client-Win64-Shipping.exe";
+        let (region, offset) = parse_maps_line(line).unwrap();
+        assert_eq!(region.base, 0x1_4000_0000);
+        assert_eq!(region.size, 0x1000);
+        assert_eq!(offset, 0);
+        assert_eq!(
+            region.module.as_deref(),
+            Some("/home/user/Games/Wuthering Waves/Client/Binaries/Win64/Client-Win64-Shipping.exe")
+        );
+        assert!(region.protection.read && !region.protection.write);
+    }
+
+    #[test]
+    fn strips_deleted_suffix() {
+        let line = "140001000-145000000 r-xp 00001000 00:2a 1234   /games/Client-Win64-Shipping.exe (deleted)\n";
+        let (region, offset) = parse_maps_line(line).unwrap();
+        assert_eq!(offset, 0x1000);
+        assert_eq!(region.module.as_deref(), Some("/games/Client-Win64-Shipping.exe"));
+        assert!(region.protection.execute);
+    }
+
+    #[test]
+    fn anonymous_and_pseudo_mappings_have_no_module() {
+        let (reserved, _) = parse_maps_line("7f0000000000-7f0000100000 ---p 00000000 00:00 0").unwrap();
+        assert_eq!(reserved.module, None);
+        assert_eq!(reserved.state, PageState::Reserve);
+
+        let (heap, _) = parse_maps_line("01a2b000-01a4c000 rw-p 00000000 00:00 0                          [heap]").unwrap();
+        assert_eq!(heap.module, None);
+        assert_eq!(heap.state, PageState::Commit);
+        assert!(heap.protection.write);
+
+        assert!(parse_maps_line("not a maps line").is_none());
+        assert!(parse_maps_line("2000-1000 r--p 00000000 00:00 0").is_none());
+    }
+
+    #[test]
+    fn matches_wine_arg0() {
+        let cmdline = b"C:\\Wuthering Waves\\Client\\Binaries\\Win64\\Client-Win64-Shipping.exe\0-dx12\0";
+        assert!(arg0_matches(cmdline, "Client-Win64-Shipping.exe"));
+        assert!(arg0_matches(b"/opt/game/client-win64-shipping.exe\0", "Client-Win64-Shipping.exe"));
+        assert!(!arg0_matches(b"wine64\0Client-Win64-Shipping.exe\0", "Client-Win64-Shipping.exe"));
+        assert!(!arg0_matches(b"", "Client-Win64-Shipping.exe"));
+    }
+
+    #[test]
+    fn matches_truncated_comm() {
+        assert!(comm_matches(b"Client-Win64-Sh\n", "Client-Win64-Shipping.exe"));
+        assert!(!comm_matches(b"Client-Win64-Sx\n", "Client-Win64-Shipping.exe"));
+        assert!(comm_matches(b"wine\n", "wine"));
+        assert!(!comm_matches(b"wine64\n", "wine"));
+        assert!(!comm_matches(b"\n", "wine"));
+    }
+}
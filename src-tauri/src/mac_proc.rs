@@ -1,12 +1,13 @@
-use crate::offsets::WuwaOffset;
-use crate::process_backend::ProcessBackend;
-use crate::types::NativeError;
-use crate::types::NativeError::PointerChainError;
+use crate::analysis_cache::{AnalysisCache, BuildKey, ResolvedSymbol};
+use crate::game_version::GameBuild;
+use crate::macho_scan;
+use crate::offsets::{GWorldScanConfig, WuwaOffset};
+use crate::process_backend::{ProcessBackend, classify_ptr};
+use crate::types::{MemoryRegion, NativeError, PageState, Protection, PtrClass, ReadFailure, ReadFault};
 use anyhow::{Context, Result, bail};
 use goblin::mach::constants::cputype;
 use goblin::mach::load_command::CommandVariant;
 use goblin::mach::{Mach, MachO, SingleArch};
-use serde::{Deserialize, Serialize};
 use std::ffi::CStr;
 use std::fs;
 use std::os::raw::{c_char, c_int, c_uint, c_void};
@@ -21,15 +22,24 @@ type Natural = u32;
 type MachMsgTypeNumber = u32;
 
 const KERN_SUCCESS: KernReturn = 0;
+const KERN_INVALID_ADDRESS: KernReturn = 1;
+const KERN_PROTECTION_FAILURE: KernReturn = 2;
+const KERN_INVALID_NAME: KernReturn = 15;
+const MACH_SEND_INVALID_DEST: KernReturn = 0x1000_0003;
 const MACH_PORT_NULL: MachPort = 0;
 const MACH_EXECUTE_BASE_VMADDR: u64 = 0x1000_0000_0;
 const GWORLD_SYMBOL_NAME: &str = "_GWorld";
 const PROC_PIDPATHINFO_MAXSIZE: usize = 4096;
-const CACHE_FILE: &str = "mac_gworld_symbol_cache.json";
 const TASK_DYLD_INFO: c_int = 17;
 const TASK_DYLD_INFO_COUNT: MachMsgTypeNumber =
     (std::mem::size_of::<TaskDyldInfo>() / std::mem::size_of::<Natural>()) as MachMsgTypeNumber;
 const MAX_REMOTE_PATH_LEN: usize = 4096;
+const VM_REGION_BASIC_INFO_64: c_int = 9;
+const VM_REGION_BASIC_INFO_COUNT_64: MachMsgTypeNumber =
+    (std::mem::size_of::<VmRegionBasicInfo64>() / std::mem::size_of::<c_int>()) as MachMsgTypeNumber;
+const VM_PROT_READ: c_int = 0x1;
+const VM_PROT_WRITE: c_int = 0x2;
+const VM_PROT_EXECUTE: c_int = 0x4;
 
 unsafe extern "C" {
     static mach_task_self_: MachPort;
@@ -49,6 +59,16 @@ unsafe extern "C" {
         data: MachVmAddress,
         out_size: *mut MachVmSize,
     ) -> KernReturn;
+    fn mach_vm_region(
+        target_task: MachPort,
+        address: *mut MachVmAddress,
+        size: *mut MachVmSize,
+        flavor: c_int,
+        info: *mut c_int,
+        info_count: *mut MachMsgTypeNumber,
+        object_name: *mut MachPort,
+    ) -> KernReturn;
+    fn proc_regionfilename(pid: c_int, address: u64, buffer: *mut c_void, buffersize: u32) -> c_int;
     fn proc_listallpids(buffer: *mut c_void, buffersize: c_int) -> c_int;
     fn proc_name(pid: c_int, buffer: *mut c_void, buffersize: c_uint) -> c_int;
     fn proc_pidpath(pid: c_int, buffer: *mut c_void, buffersize: c_uint) -> c_int;
@@ -58,29 +78,16 @@ unsafe extern "C" {
 pub struct MacProc {
     pid: c_int,
     task: MachTaskPort,
+    load_addr: u64,
     gworld_symbol_addr: u64,
+    game_build: Option<GameBuild>,
+    build_key: BuildKey,
 }
 
 struct MachTaskPort {
     port: MachPort,
 }
 
-#[derive(Default, Serialize, Deserialize)]
-struct GworldSymbolCache {
-    entries: Vec<GworldSymbolCacheEntry>,
-}
-
-#[derive(Clone, Serialize, Deserialize)]
-struct GworldSymbolCacheEntry {
-    path: String,
-    len: u64,
-    modified_secs: u64,
-    #[serde(default)]
-    uuid: Option<String>,
-    symbol: String,
-    address: u64,
-}
-
 #[repr(C)]
 #[derive(Copy, Clone, Default)]
 struct TaskDyldInfo {
@@ -89,6 +96,21 @@ struct TaskDyldInfo {
     all_image_info_format: c_int,
 }
 
+/// vm_region_basic_info_64 (#pragma pack(4)). protection 외의 필드는 레이아웃 때문에 둔다.
+#[repr(C, packed(4))]
+#[allow(dead_code)]
+#[derive(Copy, Clone, Default)]
+struct VmRegionBasicInfo64 {
+    protection: c_int,
+    max_protection: c_int,
+    inheritance: c_uint,
+    shared: c_uint,
+    reserved: c_uint,
+    offset: u64,
+    behavior: c_int,
+    user_wired_count: u16,
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Default)]
 struct DyldAllImageInfosPrefix {
@@ -106,14 +128,25 @@ struct DyldImageInfo {
 }
 
 impl MacProc {
-    pub fn new(name: &str, cache_dir: PathBuf, _scan_config: Option<crate::offsets::GWorldScanConfig>) -> Result<Self> {
+    pub fn new(
+        name: &str,
+        cache_dir: PathBuf,
+        scan_config: Option<GWorldScanConfig>,
+        _variants: &[WuwaOffset],
+    ) -> Result<Self> {
         let pid = Self::find_pid_by_name(name)
             .with_context(|| "게임이 실행 중이 아닙니다.".to_string())?;
         let task = MachTaskPort::open(pid)?;
         let process_path = Self::process_path(pid)?;
         let load_addr = Self::load_address(&task, &process_path)?;
+        let uuid = Self::macho_uuid(&process_path).unwrap_or_else(|e| {
+            log::warn!("Failed to read Mach-O UUID: {}", e);
+            None
+        });
+        let game_build = uuid.as_deref().map(GameBuild::from_macho_uuid);
+        let build_key = Self::exe_build_key(&process_path, uuid.as_deref())?;
         let gworld_file_addr =
-            Self::symbol_address_with_cache(&process_path, GWORLD_SYMBOL_NAME, &cache_dir)?;
+            Self::gworld_address_with_cache(&process_path, &build_key, scan_config.as_ref(), &cache_dir)?;
         let slide = load_addr
             .checked_sub(MACH_EXECUTE_BASE_VMADDR)
             .with_context(|| format!("잘못된 Mach-O Load Address: {:X}", load_addr))?;
@@ -122,17 +155,21 @@ impl MacProc {
             .context("_GWorld 런타임 주소 계산 overflow")?;
 
         log::info!(
-            "Process '{}' connected! PID: {}, Load Address: {:X}, _GWorld: {:X}",
+            "Process '{}' connected! PID: {}, Load Address: {:X}, Build: {}, _GWorld: {:X}",
             name,
             pid,
             load_addr,
+            game_build.as_ref().map(|b| b.to_string()).unwrap_or_else(|| "?".to_string()),
             gworld_symbol_addr
         );
 
         Ok(Self {
             pid,
             task,
+            load_addr,
             gworld_symbol_addr,
+            game_build,
+            build_key,
         })
     }
 
@@ -251,76 +288,62 @@ impl MacProc {
         })
     }
 
-    fn symbol_address_with_cache(
+    /// 분석 캐시/variant 통계 키. UUID가 없는 바이너리는 파일 크기 + 수정 시각으로 빌드를 구분한다.
+    fn exe_build_key(path: &str, uuid: Option<&str>) -> Result<BuildKey> {
+        let metadata = fs::metadata(path).context("게임 실행 파일 metadata 확인 실패")?;
+        let build_id = match uuid {
+            Some(uuid) => GameBuild::from_macho_uuid(uuid).build_id,
+            None => {
+                let modified_secs = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|value| value.duration_since(UNIX_EPOCH).ok())
+                    .map(|value| value.as_secs())
+                    .unwrap_or_default();
+                format!("file:{:X}-{:X}", metadata.len(), modified_secs)
+            }
+        };
+        Ok(BuildKey { exe_path: path.to_string(), build_id })
+    }
+
+    /// _GWorld 심볼을 찾고, 심볼이 없는(stripped) 빌드는 __TEXT,__text를 시그니처로 스캔한다.
+    fn gworld_address_with_cache(
         path: impl AsRef<Path>,
-        symbol: &str,
+        key: &BuildKey,
+        scan_config: Option<&GWorldScanConfig>,
         cache_dir: &Path,
     ) -> Result<u64> {
         let path = path.as_ref();
-        let metadata = fs::metadata(path).context("게임 실행 파일 metadata 확인 실패")?;
-        let path_string = path.to_string_lossy().into_owned();
-        let uuid = Self::macho_uuid(path).unwrap_or_else(|e| {
-            log::warn!("Failed to read Mach-O UUID for cache key: {}", e);
-            None
-        });
-        let modified_secs = metadata
-            .modified()
-            .ok()
-            .and_then(|value| value.duration_since(UNIX_EPOCH).ok())
-            .map(|value| value.as_secs())
-            .unwrap_or_default();
-        let cache_path = cache_dir.join(CACHE_FILE);
-        let mut cache = Self::load_symbol_cache(&cache_path);
-
-        if let Some(entry) = cache.entries.iter().find(|entry| {
-            entry.path == path_string
-                && entry.len == metadata.len()
-                && entry.modified_secs == modified_secs
-                && entry.uuid == uuid
-                && entry.symbol == symbol
-        }) {
-            log::info!("Using cached Mach-O symbol {}: {:X}", symbol, entry.address);
-            return Ok(entry.address);
+        let mut cache = AnalysisCache::open(cache_dir);
+
+        if let Some(cached) = cache.lookup(key, GWORLD_SYMBOL_NAME) {
+            log::info!(
+                "Using cached Mach-O symbol {}: {:X} [{}]",
+                GWORLD_SYMBOL_NAME,
+                cached.address,
+                cached.source.as_deref().unwrap_or("-")
+            );
+            return Ok(cached.address);
         }
 
-        let address = Self::symbol_address(path, symbol)?;
-        cache
-            .entries
-            .retain(|entry| !(entry.path == path_string && entry.symbol == symbol));
-        cache.entries.push(GworldSymbolCacheEntry {
-            path: path_string,
-            len: metadata.len(),
-            modified_secs,
-            uuid,
-            symbol: symbol.to_string(),
-            address,
-        });
-        Self::save_symbol_cache(&cache_path, &cache);
-        Ok(address)
-    }
-
-    fn load_symbol_cache(path: &Path) -> GworldSymbolCache {
-        let Ok(data) = fs::read_to_string(path) else {
-            return GworldSymbolCache::default();
-        };
-        serde_json::from_str(&data).unwrap_or_default()
-    }
-
-    fn save_symbol_cache(path: &Path, cache: &GworldSymbolCache) {
-        if let Some(parent) = path.parent() {
-            if let Err(e) = fs::create_dir_all(parent) {
-                log::warn!("Failed to create symbol cache dir: {}", e);
-                return;
+        let (address, source) = match Self::symbol_address(path, GWORLD_SYMBOL_NAME) {
+            Ok(address) => (address, "symtab".to_string()),
+            Err(e) => {
+                let Some(scan_config) = scan_config.filter(|c| c.enabled) else {
+                    return Err(e);
+                };
+                log::warn!("{:#} → __TEXT,__text 시그니처 스캔", e);
+                let bytes = fs::read(path).context("게임 Mach-O 파일 읽기 실패")?;
+                let result = macho_scan::scan_gworld(&bytes, Some(current_cputype()), &scan_config.signatures())
+                    .context("GWorld 시그니처 스캔 실패")?;
+                (result.gworld_address, result.signature)
             }
-        }
-        match serde_json::to_string(cache) {
-            Ok(data) => {
-                if let Err(e) = fs::write(path, data) {
-                    log::warn!("Failed to write symbol cache: {}", e);
-                }
-            }
-            Err(e) => log::warn!("Failed to serialize symbol cache: {}", e),
-        }
+        };
+        cache.insert(
+            key,
+            ResolvedSymbol { name: GWORLD_SYMBOL_NAME.to_string(), address, source: Some(source) },
+        );
+        Ok(address)
     }
 
     fn symbol_address(path: impl AsRef<Path>, symbol: &str) -> Result<u64> {
@@ -405,6 +428,54 @@ impl MacProc {
             }
         })
     }
+
+    /// address를 포함하거나 address 뒤에 있는 첫 영역. 실패하면 kern_return
+    /// (KERN_INVALID_ADDRESS = address 뒤로 영역이 없음)
+    fn region_from(&self, address: u64) -> Result<MemoryRegion, KernReturn> {
+        let mut base: MachVmAddress = address;
+        let mut size: MachVmSize = 0;
+        let mut info = VmRegionBasicInfo64::default();
+        let mut count = VM_REGION_BASIC_INFO_COUNT_64;
+        let mut object_name: MachPort = MACH_PORT_NULL;
+        let kr = unsafe {
+            mach_vm_region(
+                self.task.port,
+                &mut base,
+                &mut size,
+                VM_REGION_BASIC_INFO_64,
+                &mut info as *mut VmRegionBasicInfo64 as *mut c_int,
+                &mut count,
+                &mut object_name,
+            )
+        };
+        if kr != KERN_SUCCESS {
+            if kr != KERN_INVALID_ADDRESS {
+                log::debug!("mach_vm_region@{:X} 실패: kern_return={}", address, kr);
+            }
+            return Err(kr);
+        }
+        let protection = info.protection;
+        Ok(MemoryRegion {
+            base,
+            size,
+            state: PageState::Commit,
+            protection: Protection {
+                read: protection & VM_PROT_READ != 0,
+                write: protection & VM_PROT_WRITE != 0,
+                execute: protection & VM_PROT_EXECUTE != 0,
+                guard: false,
+            },
+            module: self.region_file_name(base),
+        })
+    }
+
+    fn region_file_name(&self, address: u64) -> Option<String> {
+        let mut buffer = [0u8; PROC_PIDPATHINFO_MAXSIZE];
+        let len = unsafe {
+            proc_regionfilename(self.pid, address, buffer.as_mut_ptr() as *mut c_void, buffer.len() as u32)
+        };
+        (len > 0).then(|| String::from_utf8_lossy(&buffer[..len as usize]).into_owned())
+    }
 }
 
 impl ProcessBackend for MacProc {
@@ -413,26 +484,79 @@ impl ProcessBackend for MacProc {
     }
 
     fn read_bytes(&self, address: u64, buffer: &mut [u8]) -> Result<(), NativeError> {
-        self.task
-            .read_bytes(address, buffer)
-            .map_err(|e| NativeError::ValueReadError {
-                message: e.to_string(),
-            })
+        self.task.read_bytes(address, buffer)
     }
 
     fn read_gworld(&self, _offset: &WuwaOffset) -> Result<u64, NativeError> {
         self.read_memory::<u64>(self.gworld_symbol_addr)
-            .map_err(|e| PointerChainError {
-                message: format!(
-                    "_GWorld 위치 ({:X})의 주소 값을 읽지 못했습니다: {}",
-                    self.gworld_symbol_addr, e
-                ),
+            .map_err(|e| {
+                e.in_chain("_GWorld", self.gworld_symbol_addr, 0, classify_ptr(self.gworld_symbol_addr))
             })
     }
 
     fn active_offset_name(&self, _offset: &WuwaOffset) -> String {
         format!("{:X}", self.gworld_symbol_addr)
     }
+
+    fn module_base(&self) -> u64 {
+        self.load_addr
+    }
+
+    fn game_build(&self) -> Option<&GameBuild> {
+        self.game_build.as_ref()
+    }
+
+    fn build_key(&self) -> Option<BuildKey> {
+        Some(self.build_key.clone())
+    }
+
+    // 시그니처 스캔/캐시 결과가 Load Address보다 앞이면 RVA로 표현할 수 없다.
+    fn gworld_rva(&self) -> Option<u64> {
+        self.gworld_symbol_addr.checked_sub(self.load_addr)
+    }
+
+    fn regions(&self) -> Vec<MemoryRegion> {
+        let mut regions = Vec::new();
+        let mut address = 0u64;
+        while let Ok(region) = self.region_from(address) {
+            let end = region.end();
+            regions.push(region);
+            if end <= address {
+                break;
+            }
+            address = end;
+        }
+        regions
+    }
+
+    fn query_region(&self, address: u64) -> Option<MemoryRegion> {
+        let region = match self.region_from(address) {
+            Ok(region) => region,
+            // address 뒤로 영역이 없음
+            Err(KERN_INVALID_ADDRESS) => {
+                return Some(MemoryRegion {
+                    base: address,
+                    size: u64::MAX - address,
+                    state: PageState::Free,
+                    protection: Protection::default(),
+                    module: None,
+                });
+            }
+            // 그 외 실패(태스크 포트 무효 등)는 영역을 알 수 없다.
+            Err(_) => return None,
+        };
+        if region.contains(address) {
+            return Some(region);
+        }
+        // mach_vm_region은 address 이후의 첫 영역을 돌려주므로 그 앞은 매핑되지 않은 공간이다.
+        Some(MemoryRegion {
+            base: address,
+            size: region.base - address,
+            state: PageState::Free,
+            protection: Protection::default(),
+            module: None,
+        })
+    }
 }
 
 impl MachTaskPort {
@@ -493,9 +617,9 @@ impl MachTaskPort {
         bail!("원격 문자열이 너무 깁니다.");
     }
 
-    fn read_bytes(&self, address: u64, buffer: &mut [u8]) -> Result<()> {
+    fn read_bytes(&self, address: u64, buffer: &mut [u8]) -> Result<(), NativeError> {
         if address == 0 {
-            bail!("원격 메모리 주소가 0입니다.");
+            return Err(NativeError::pointer_chain(ReadFailure::at(0).pointer(PtrClass::Null)));
         }
 
         let mut bytes_read: MachVmSize = 0;
@@ -512,12 +636,17 @@ impl MachTaskPort {
         if success == KERN_SUCCESS && bytes_read == buffer.len() as MachVmSize {
             Ok(())
         } else {
-            bail!(
-                "mach_vm_read_overwrite 실패: kern_return={}, bytes_read={}/{}",
-                success,
-                bytes_read,
-                buffer.len()
-            )
+            let fault = match success {
+                KERN_SUCCESS | KERN_INVALID_ADDRESS => ReadFault::Partial,
+                KERN_PROTECTION_FAILURE => ReadFault::AccessDenied,
+                KERN_INVALID_NAME | MACH_SEND_INVALID_DEST => ReadFault::InvalidHandle,
+                _ => ReadFault::Other,
+            };
+            Err(NativeError::value_read(
+                ReadFailure::at(address)
+                    .fault(fault, Some(success))
+                    .note(format!("{}/{} 바이트만 읽음", bytes_read, buffer.len())),
+            ))
         }
     }
 }
@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::ops::Range;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use goblin::mach::constants::cputype::{CPU_TYPE_ARM64, CPU_TYPE_X86_64};
+use goblin::mach::{Mach, MachO, SingleArch};
+use serde::Serialize;
+
+use crate::offsets::{GWorldScanConfig, GWorldSignature, SignatureArch};
+use crate::sig_scan::Pattern;
+
+const MH_MAGIC_64: u32 = 0xFEED_FACF;
+const FAT_MAGIC: u32 = 0xCAFE_BABE;
+const VM_PROT_WRITE: u32 = 0x2;
+
+/// _GWorld 심볼이 없는 Mach-O에서 __TEXT,__text를 시그니처로 스캔한 결과.
+/// 실행 중인 프로세스 없이 파일 바이트만으로 계산하므로 후보 검증은 하지 않는다.
+#[derive(Serialize, Clone, Debug)]
+pub struct MachoScanResult {
+    pub arch: SignatureArch,
+    /// 파일상의 가상 주소 (런타임 주소 = 이 값 + slide)
+    pub gworld_address: u64,
+    /// 일치한 시그니처 이름
+    pub signature: String,
+    /// 같은 주소를 가리킨 일치 수
+    pub hits: usize,
+}
+
+struct CompiledSignature<'a> {
+    name: &'a str,
+    pattern: Pattern,
+    disp_offset: usize,
+    instr_len: usize,
+}
+
+/// 파일 앞 4바이트로 Mach-O(64-bit 또는 Fat)인지 확인한다.
+pub fn is_macho_file(path: &Path) -> Result<bool> {
+    let mut magic = [0u8; 4];
+    let mut file = fs::File::open(path).with_context(|| format!("실행 파일을 열지 못했습니다: {}", path.display()))?;
+    if file.read_exact(&mut magic).is_err() {
+        return Ok(false);
+    }
+    Ok(u32::from_le_bytes(magic) == MH_MAGIC_64 || u32::from_be_bytes(magic) == FAT_MAGIC)
+}
+
+/// 디스크의 Mach-O 파일을 스캔한다. (오프라인 CLI용, Fat이면 첫 번째로 성공한 arch)
+pub fn scan_file(path: &Path, scan_config: &GWorldScanConfig) -> Result<MachoScanResult> {
+    let bytes = fs::read(path).with_context(|| format!("실행 파일을 읽지 못했습니다: {}", path.display()))?;
+    scan_gworld(&bytes, None, &scan_config.signatures())
+}
+
+/// Mach-O 바이트에서 GWorld의 파일상 가상 주소를 찾는다.
+/// Fat 바이너리는 cputype이 일치하는 arch를 쓰고, 없으면 각 arch를 순서대로 시도한다.
+pub fn scan_gworld(bytes: &[u8], cputype: Option<u32>, signatures: &[GWorldSignature]) -> Result<MachoScanResult> {
+    match Mach::parse(bytes).context("Mach-O 파싱 실패")? {
+        Mach::Binary(macho) => scan_macho(&macho, signatures),
+        Mach::Fat(fat) => {
+            if let Some(cputype) = cputype
+                && let Some(arch) = fat.find_cputype(cputype).context("Fat Mach-O 탐색 실패")?
+            {
+                // 섹션 파일 오프셋은 arch slice 기준이다.
+                let macho = MachO::parse(arch.slice(bytes), 0).context("Mach-O arch 파싱 실패")?;
+                return scan_macho(&macho, signatures);
+            }
+
+            let mut last_error = None;
+            for i in 0..fat.narches {
+                match fat.get(i) {
+                    Ok(SingleArch::MachO(macho)) => match scan_macho(&macho, signatures) {
+                        Ok(result) => return Ok(result),
+                        Err(e) => last_error = Some(e),
+                    },
+                    Ok(SingleArch::Archive(_)) => {}
+                    Err(e) => log::debug!("Skipping Mach-O arch: {}", e),
+                }
+            }
+            Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Fat Mach-O에 스캔할 arch가 없습니다.")))
+        }
+    }
+}
+
+/// 시그니처를 우선순위 순서로 시도하고, 일치가 있는 첫 시그니처에서 가장 많이 참조된 주소를 고른다.
+/// 대상 주소가 쓰기 가능한 세그먼트(__DATA 등) 밖이면 오탐으로 보고 버린다.
+fn scan_macho(macho: &MachO<'_>, signatures: &[GWorldSignature]) -> Result<MachoScanResult> {
+    let arch = match macho.header.cputype {
+        CPU_TYPE_ARM64 => SignatureArch::Arm64,
+        CPU_TYPE_X86_64 => SignatureArch::X86_64,
+        other => bail!("지원하지 않는 Mach-O CPU 타입: 0x{:X}", other),
+    };
+    let signatures = compile_signatures(signatures, arch);
+    if signatures.is_empty() {
+        bail!("사용 가능한 {:?} GWorld 시그니처가 없습니다.", arch);
+    }
+
+    let mut text = None;
+    let mut writable: Vec<Range<u64>> = Vec::new();
+    for segment in &macho.segments {
+        if segment.initprot & VM_PROT_WRITE != 0 {
+            writable.push(segment.vmaddr..segment.vmaddr + segment.vmsize);
+        }
+        if segment.name().ok() != Some("__TEXT") {
+            continue;
+        }
+        for (section, data) in segment.sections().context("__TEXT 섹션 읽기 실패")? {
+            if section.name().ok() == Some("__text") {
+                text = Some((section.addr, data));
+            }
+        }
+    }
+    let (text_addr, code) = text.context("__TEXT,__text 섹션을 찾지 못했습니다.")?;
+    if writable.is_empty() {
+        bail!("쓰기 가능한 세그먼트가 없습니다.");
+    }
+    log::debug!("Mach-O {:?}: __text 0x{:X}+0x{:X}, 쓰기 가능 {:X?}", arch, text_addr, code.len(), writable);
+
+    for sig in &signatures {
+        let mut hits: HashMap<u64, usize> = HashMap::new();
+        for off in sig.pattern.find_iter(code) {
+            let target = match arch {
+                SignatureArch::X86_64 => Some(sig.rip_target(code, off, text_addr)),
+                SignatureArch::Arm64 => sig.adrp_target(code, off, text_addr),
+            };
+            if let Some(target) = target
+                && writable.iter().any(|r| r.contains(&target))
+            {
+                *hits.entry(target).or_default() += 1;
+            }
+        }
+        // 참조 수가 같으면 낮은 주소 (결과가 실행마다 달라지지 않도록)
+        if let Some((address, count)) = hits
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+            .map(|(a, c)| (*a, *c))
+        {
+            log::info!(
+                "Mach-O GWorld 스캔: '{}' → 0x{:X} ({}회 참조, 후보 주소 {}개)",
+                sig.name,
+                address,
+                count,
+                hits.len()
+            );
+            return Ok(MachoScanResult { arch, gworld_address: address, signature: sig.name.to_string(), hits: count });
+        }
+    }
+    bail!("__TEXT,__text에서 GWorld 시그니처가 일치하지 않았습니다.")
+}
+
+fn compile_signatures(signatures: &[GWorldSignature], arch: SignatureArch) -> Vec<CompiledSignature<'_>> {
+    signatures
+        .iter()
+        .filter(|sig| sig.arch == arch)
+        .filter_map(|sig| {
+            let pattern = match Pattern::parse(&sig.pattern) {
+                Ok(p) => p,
+                Err(e) => {
+                    log::warn!("GWorld 시그니처 '{}' 무시: {}", sig.name, e);
+                    return None;
+                }
+            };
+            let valid = match arch {
+                SignatureArch::X86_64 => {
+                    sig.disp_offset + 4 <= pattern.len() && sig.instr_len >= sig.disp_offset + 4
+                }
+                // ADRP와 다음 명령어가 모두 패턴 안에 있어야 한다.
+                SignatureArch::Arm64 => sig.disp_offset.is_multiple_of(4) && sig.disp_offset + 8 <= pattern.len(),
+            };
+            if !valid {
+                log::warn!("GWorld 시그니처 '{}' 무시: 잘못된 disp_offset/instr_len", sig.name);
+                return None;
+            }
+            Some(CompiledSignature {
+                name: &sig.name,
+                pattern,
+                disp_offset: sig.disp_offset,
+                instr_len: sig.instr_len,
+            })
+        })
+        .collect()
+}
+
+impl CompiledSignature<'_> {
+    /// RIP-relative disp32의 대상 주소 (code_addr = code[0]의 가상 주소)
+    fn rip_target(&self, code: &[u8], off: usize, code_addr: u64) -> u64 {
+        let disp_at = off + self.disp_offset;
+        let disp = i32::from_le_bytes(code[disp_at..disp_at + 4].try_into().unwrap());
+        let instr_addr = code_addr + off as u64;
+        ((instr_addr as i64) + self.instr_len as i64 + disp as i64) as u64
+    }
+
+    /// ADRP Xn, page + LDR Xt, [Xn, #imm] / ADD Xd, Xn, #imm 의 대상 주소.
+    /// 명령어 경계가 아니거나 두 명령어가 이어지지 않으면 None
+    fn adrp_target(&self, code: &[u8], off: usize, code_addr: u64) -> Option<u64> {
+        let at = off + self.disp_offset;
+        if !at.is_multiple_of(4) {
+            return None;
+        }
+        let adrp = u32::from_le_bytes(code[at..at + 4].try_into().unwrap());
+        let next = u32::from_le_bytes(code[at + 4..at + 8].try_into().unwrap());
+        let (rd, page) = decode_adrp(adrp, code_addr + at as u64)?;
+        let (rn, page_offset) = decode_page_offset(next)?;
+        (rn == rd).then(|| page.wrapping_add(page_offset))
+    }
+}
+
+/// ADRP Xd, label → (Xd, 페이지 주소)
+fn decode_adrp(insn: u32, pc: u64) -> Option<(u32, u64)> {
+    if insn & 0x9F00_0000 != 0x9000_0000 {
+        return None;
+    }
+    let immlo = (insn >> 29) & 0x3;
+    let immhi = (insn >> 5) & 0x7_FFFF;
+    // 21비트 부호 확장 후 << 12
+    let imm = ((((immhi << 2) | immlo) as i64) << 43) >> 31;
+    Some((insn & 0x1F, (pc & !0xFFF).wrapping_add(imm as u64)))
+}
+
+/// 페이지 안 오프셋을 더하는 명령어 → (Xn, 오프셋)
+///   LDR Xt, [Xn, #imm12 * 8] (64-bit, unsigned offset)
+///   ADD Xd, Xn, #imm12       (64-bit, shift 없음)
+fn decode_page_offset(insn: u32) -> Option<(u32, u64)> {
+    let rn = (insn >> 5) & 0x1F;
+    let imm12 = ((insn >> 10) & 0xFFF) as u64;
+    match insn & 0xFFC0_0000 {
+        0xF940_0000 => Some((rn, imm12 * 8)),
+        0x9100_0000 => Some((rn, imm12)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GWORLD_ARM64: u64 = 0x1_0000_4A08;
+    const GWORLD_X86_64: u64 = 0x1_0000_4C10;
+
+    fn fixture(name: &str) -> Vec<u8> {
+        fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/macho").join(name)).unwrap()
+    }
+
+    fn signature(name: &str, arch: SignatureArch, pattern: &str, disp_offset: usize, instr_len: usize) -> GWorldSignature {
+        GWorldSignature { name: name.to_string(), pattern: pattern.to_string(), disp_offset, instr_len, arch }
+    }
+
+    /// ADRP x8 + LDR x8, [x8, #imm] + CBZ x8
+    fn adrp_ldr() -> GWorldSignature {
+        signature("adrp-ldr", SignatureArch::Arm64, "?? ?? ?? ?? ?? ?? ?? F9 08 01 00 B4", 0, 0)
+    }
+
+    /// mov rbx, [rip+disp32]; test rbx, rbx; je; mov r8b, 1
+    fn rip_mov() -> GWorldSignature {
+        signature("rip-mov", SignatureArch::X86_64, "48 8B 1D ?? ?? ?? ?? 48 85 DB 74 ?? 41 B0 01", 3, 7)
+    }
+
+    #[test]
+    fn decodes_adrp_page() {
+        assert_eq!(decode_adrp(0xF000_0008, 0x1_0000_1234), Some((8, 0x1_0000_4000)));
+        // 음수 페이지 오프셋 (-4 페이지)
+        assert_eq!(decode_adrp(0x90FF_FFE0, 0x1_0000_5000), Some((0, 0x1_0000_1000)));
+        // ADR은 ADRP가 아니다.
+        assert_eq!(decode_adrp(0x1000_0008, 0x1_0000_1000), None);
+    }
+
+    #[test]
+    fn decodes_page_offset() {
+        assert_eq!(decode_page_offset(0xF945_0508), Some((8, 0xA08)));
+        assert_eq!(decode_page_offset(0x9100_414A), Some((10, 0x10)));
+        assert_eq!(decode_page_offset(0xD503_201F), None);
+    }
+
+    #[test]
+    fn scans_arm64_adrp_ldr() {
+        let result = scan_gworld(&fixture("arm64.bin"), None, &[adrp_ldr()]).unwrap();
+        assert_eq!(result.arch, SignatureArch::Arm64);
+        assert_eq!(result.gworld_address, GWORLD_ARM64);
+        // 레지스터가 다른 ADRP와 __TEXT를 가리키는 일치는 세지 않는다.
+        assert_eq!(result.hits, 3);
+    }
+
+    #[test]
+    fn scans_arm64_adrp_add() {
+        let add = signature("adrp-add", SignatureArch::Arm64, "?? ?? ?? ?? 4A 41 00 91 1F 20 03 D5", 0, 0);
+        let result = scan_gworld(&fixture("arm64.bin"), None, &[add]).unwrap();
+        assert_eq!(result.gworld_address, 0x1_0000_5010);
+        assert_eq!(result.signature, "adrp-add");
+    }
+
+    #[test]
+    fn scans_x86_64_rip_relative() {
+        let result = scan_gworld(&fixture("x86_64.bin"), None, &[adrp_ldr(), rip_mov()]).unwrap();
+        assert_eq!(result.arch, SignatureArch::X86_64);
+        assert_eq!(result.gworld_address, GWORLD_X86_64);
+        assert_eq!(result.hits, 2);
+    }
+
+    #[test]
+    fn selects_fat_arch() {
+        let fat = fixture("fat.bin");
+        let signatures = [adrp_ldr(), rip_mov()];
+
+        let arm64 = scan_gworld(&fat, Some(CPU_TYPE_ARM64), &signatures).unwrap();
+        assert_eq!((arm64.arch, arm64.gworld_address), (SignatureArch::Arm64, GWORLD_ARM64));
+        let x86_64 = scan_gworld(&fat, Some(CPU_TYPE_X86_64), &signatures).unwrap();
+        assert_eq!((x86_64.arch, x86_64.gworld_address), (SignatureArch::X86_64, GWORLD_X86_64));
+        // 선호 arch가 없으면 시그니처가 맞는 첫 arch (x86_64 slice에는 arm64 시그니처만으로는 실패)
+        let fallback = scan_gworld(&fat, None, &[adrp_ldr()]).unwrap();
+        assert_eq!(fallback.arch, SignatureArch::Arm64);
+    }
+
+    #[test]
+    fn rejects_targets_outside_writable_segments() {
+        // LDR #0x800 일치 하나뿐이고, 대상이 __TEXT(r-x)이다.
+        let text_only = signature("text", SignatureArch::Arm64, "?? ?? ?? ?? 08 01 44 F9 08 01 00 B4", 0, 0);
+        assert!(scan_gworld(&fixture("arm64.bin"), None, &[text_only]).is_err());
+    }
+
+    #[test]
+    fn detects_macho_files() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        assert!(is_macho_file(&dir.join("macho/arm64.bin")).unwrap());
+        assert!(is_macho_file(&dir.join("macho/fat.bin")).unwrap());
+        assert!(!is_macho_file(&dir.join("snapshot_legacy_chain.json")).unwrap());
+    }
+}
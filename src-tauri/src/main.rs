@@ -2,5 +2,9 @@
 #![cfg_attr(all(windows, not(debug_assertions)), windows_subsystem = "windows")]
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = wuma_tracker_lib::run_cli(&args) {
+        std::process::exit(code);
+    }
     wuma_tracker_lib::run()
 }
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use crate::offsets::{ChainValue, NameConfig, WuwaOffset};
+use crate::process_backend::{ProcessBackend, resolve_chain};
+use crate::types::{NativeError, ReadFailure};
+
+// FNameEntryHeader의 bIsWide 비트
+const HEADER_WIDE_BIT: u16 = 1;
+// 비정상 길이로 엉뚱한 메모리를 대량으로 읽지 않도록 제한 (NAME_SIZE = 1024)
+const MAX_NAME_LEN: usize = 1024;
+
+/// FNamePool(GNames)에서 FName을 문자열로 해석한다.
+/// ComparisonIndex는 프로세스가 살아 있는 동안 바뀌지 않으므로 결과를 캐시한다.
+pub struct FNameResolver {
+    config: NameConfig,
+    cache: HashMap<u32, String>,
+}
+
+impl FNameResolver {
+    pub fn new(config: NameConfig) -> Self {
+        Self { config, cache: HashMap::new() }
+    }
+
+    pub fn config(&self) -> &NameConfig {
+        &self.config
+    }
+
+    /// PersistentLevel의 Outer(UWorld) 등 설정된 UObject의 이름을 읽는다.
+    pub fn read_map_name<B: ProcessBackend>(
+        &mut self,
+        backend: &B,
+        offset: &WuwaOffset,
+    ) -> Result<String, NativeError> {
+        let gworld = backend.read_gworld(offset)?;
+        let steps = self.config.map_name_chain(offset);
+        let fname_addr = resolve_chain(backend, gworld, &steps, ChainValue::Name)?;
+        self.read_fname(backend, fname_addr)
+    }
+
+    /// FName { ComparisonIndex: u32, Number: u32 } 을 읽어 "Name" 또는 "Name_{Number-1}"로 만든다.
+    pub fn read_fname<B: ProcessBackend>(&mut self, backend: &B, address: u64) -> Result<String, NativeError> {
+        let fname = backend.read_memory::<[u32; 2]>(address).map_err(|e| e.in_value("fname"))?;
+        let [index, number] = fname;
+        let base = self.resolve_index(backend, index)?;
+        Ok(if number > 0 {
+            format!("{}_{}", base, number - 1)
+        } else {
+            base
+        })
+    }
+
+    fn resolve_index<B: ProcessBackend>(&mut self, backend: &B, index: u32) -> Result<String, NativeError> {
+        if let Some(name) = self.cache.get(&index) {
+            return Ok(name.clone());
+        }
+
+        // 설정이나 메모리가 잘못되어도 넘치지 않게 감싸 더한다. 엉뚱한 주소는 읽기 실패로 보고된다.
+        let config = &self.config;
+        let pool = backend.module_base().wrapping_add(config.gnames);
+        let block = (index >> 16) as u64;
+        let block_offset = ((index & 0xFFFF) as u64).wrapping_mul(config.entry_stride);
+
+        let blocks_addr = pool.wrapping_add(config.pool_blocks).wrapping_add(block * 8);
+        let block_ptr = backend.read_memory::<u64>(blocks_addr).map_err(|e| e.in_value(&format!("fname#{:X} block", index)))?;
+
+        let entry = block_ptr.wrapping_add(block_offset);
+        let header = backend.read_memory::<u16>(entry).map_err(|e| e.in_value(&format!("fname#{:X} entry", index)))?;
+        let len = header.checked_shr(config.header_len_shift).unwrap_or(0) as usize;
+        if len == 0 || len > MAX_NAME_LEN {
+            return Err(NativeError::value_read(
+                ReadFailure::at(entry)
+                    .stage(format!("fname#{:X} entry", index))
+                    .note(format!("비정상 길이 {}", len)),
+            ));
+        }
+
+        let name = if header & HEADER_WIDE_BIT != 0 {
+            let mut buf = vec![0u8; len * 2];
+            backend.read_bytes(entry.wrapping_add(2), &mut buf)?;
+            let wide: Vec<u16> = buf
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&wide)
+        } else {
+            let mut buf = vec![0u8; len];
+            backend.read_bytes(entry.wrapping_add(2), &mut buf)?;
+            // ANSI 엔트리는 Latin-1
+            buf.iter().map(|&b| b as char).collect()
+        };
+
+        self.cache.insert(index, name.clone());
+        Ok(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot_proc::{MemorySnapshot, SnapshotProc, SnapshotRegion};
+
+    const MODULE_BASE: u64 = 0x1_4000_0000;
+    const BLOCK0: u64 = 0x2_0000_0000;
+    const BLOCK1: u64 = 0x2_0001_0000;
+    const FNAMES: u64 = 0x3_0000_0000;
+
+    fn config(header_len_shift: u32) -> NameConfig {
+        NameConfig {
+            gnames: 0x100,
+            pool_blocks: 0x10,
+            entry_stride: 2,
+            header_len_shift,
+            uobject_name: 0x18,
+            uobject_outer: 0x20,
+            map_name: vec![],
+        }
+    }
+
+    fn entry(header: u16, name: &[u8]) -> Vec<u8> {
+        let mut bytes = header.to_le_bytes().to_vec();
+        bytes.extend_from_slice(name);
+        bytes
+    }
+
+    fn fname(index: u32, number: u32) -> Vec<u8> {
+        [index.to_le_bytes(), number.to_le_bytes()].concat()
+    }
+
+    /// Blocks[0..3]와 엔트리, FName 몇 개가 기록된 스냅샷. (Len 비트는 6번째부터)
+    /// - #0004: ANSI "Jinzhou", #0010: wide "월드", #0020: 길이 0
+    /// - #1_0002: 두 번째 블록의 ANSI "Main"
+    /// - #2_0000: Blocks[2]가 주소 공간 끝을 가리킨다.
+    fn fixture() -> SnapshotProc {
+        let wide: Vec<u8> = "월드".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let region = |address, bytes| SnapshotRegion { address, bytes };
+        SnapshotProc::new(MemorySnapshot {
+            module_base: MODULE_BASE,
+            gworld_rva: None,
+            regions: vec![
+                region(
+                    MODULE_BASE + 0x110,
+                    [BLOCK0, BLOCK1, u64::MAX - 1].iter().flat_map(|p| p.to_le_bytes()).collect(),
+                ),
+                region(BLOCK0 + 0x08, entry(7 << 6, b"Jinzhou")),
+                region(BLOCK0 + 0x20, entry((2 << 6) | HEADER_WIDE_BIT, &wide)),
+                region(BLOCK0 + 0x40, entry(0, b"")),
+                region(BLOCK1 + 0x04, entry(4 << 6, b"Main")),
+                region(FNAMES, fname(0x0004, 0)),
+                region(FNAMES + 0x10, fname(0x0004, 3)),
+                region(FNAMES + 0x20, fname(0x0010, 0)),
+                region(FNAMES + 0x30, fname(0x0020, 0)),
+                region(FNAMES + 0x40, fname(0x1_0002, 0)),
+                region(FNAMES + 0x50, fname(0x2_0000, 0)),
+            ],
+        })
+    }
+
+    #[test]
+    fn reads_ansi_and_wide_entries() {
+        let proc = fixture();
+        let mut resolver = FNameResolver::new(config(6));
+        assert_eq!(resolver.read_fname(&proc, FNAMES).unwrap(), "Jinzhou");
+        assert_eq!(resolver.read_fname(&proc, FNAMES + 0x20).unwrap(), "월드");
+        assert_eq!(resolver.read_fname(&proc, FNAMES + 0x40).unwrap(), "Main");
+    }
+
+    #[test]
+    fn appends_number_suffix() {
+        let proc = fixture();
+        let mut resolver = FNameResolver::new(config(6));
+        assert_eq!(resolver.read_fname(&proc, FNAMES + 0x10).unwrap(), "Jinzhou_2");
+    }
+
+    #[test]
+    fn rejects_bad_lengths() {
+        let proc = fixture();
+        let mut resolver = FNameResolver::new(config(6));
+        let err = resolver.read_fname(&proc, FNAMES + 0x30).unwrap_err();
+        assert_eq!(err.failure().map(|f| f.address), Some(BLOCK0 + 0x40));
+
+        // Len 비트가 6번째부터면 1024를 넘을 수 없으므로, 1번째부터 읽는 설정으로 확인한다.
+        let long = SnapshotProc::new(MemorySnapshot {
+            module_base: MODULE_BASE,
+            gworld_rva: None,
+            regions: vec![
+                SnapshotRegion { address: MODULE_BASE + 0x110, bytes: BLOCK0.to_le_bytes().to_vec() },
+                SnapshotRegion { address: BLOCK0, bytes: entry(((MAX_NAME_LEN as u16) + 1) << 1, b"") },
+                SnapshotRegion { address: FNAMES, bytes: fname(0, 0) },
+            ],
+        });
+        let err = FNameResolver::new(config(1)).read_fname(&long, FNAMES).unwrap_err();
+        assert_eq!(err.failure().map(|f| f.address), Some(BLOCK0));
+        assert!(err.failure().and_then(|f| f.note.as_deref()).is_some_and(|n| n.contains("1025")));
+    }
+
+    #[test]
+    fn wrapped_entry_address_fails_to_read() {
+        let proc = fixture();
+        let mut resolver = FNameResolver::new(config(6));
+        assert!(resolver.read_fname(&proc, FNAMES + 0x50).is_err());
+    }
+
+    #[test]
+    fn resolved_names_are_cached() {
+        let proc = fixture();
+        let mut resolver = FNameResolver::new(config(6));
+        assert_eq!(resolver.read_fname(&proc, FNAMES).unwrap(), "Jinzhou");
+
+        // 풀이 없는 스냅샷에서도 FName만 읽히면 캐시된 이름을 돌려준다.
+        let without_pool = SnapshotProc::new(MemorySnapshot {
+            module_base: MODULE_BASE,
+            gworld_rva: None,
+            regions: vec![SnapshotRegion { address: FNAMES, bytes: fname(0x0004, 1) }],
+        });
+        assert_eq!(resolver.read_fname(&without_pool, FNAMES).unwrap(), "Jinzhou_0");
+        assert!(FNameResolver::new(config(6)).read_fname(&without_pool, FNAMES).is_err());
+    }
+}
@@ -1,19 +1,24 @@
+#[cfg(target_os = "linux")]
+use crate::linux_proc::LinuxProc as PlatformProc;
 #[cfg(target_os = "macos")]
 use crate::mac_proc::MacProc as PlatformProc;
-use crate::offsets::{GWorldScanConfig, TrackerConfig, WuwaOffset};
-use crate::process_backend::{ProcessBackend, select_player_info};
-use crate::types::NativeError::PointerChainError;
-use crate::types::{CollectorMessage, NativeError};
+use crate::diagnostics::{self, ChainReport};
+use crate::name_resolver::FNameResolver;
+use crate::offsets::{GWorldScanConfig, NameConfig, TrackerConfig, WuwaOffset};
+use crate::process_backend::{ProcessBackend, ResolvedPlayer, read_resolved_player, select_player_info};
+use crate::snapshot_proc::{MemoryRecorder, MemorySnapshot, SnapshotProc};
+use crate::types::{CollectorMessage, NativeError, PlayerInfo, TrackerError};
+use crate::variant_stats::VariantStats;
 #[cfg(windows)]
 use crate::win_proc::WinProc as PlatformProc;
 
-#[cfg(not(any(windows, target_os = "macos")))]
-compile_error!("Native process tracking is supported only on Windows and macOS.");
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+compile_error!("Native process tracking is supported only on Windows, macOS and Linux (Wine/Proton).");
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{Mutex, mpsc};
+use tokio::sync::{Mutex, mpsc, watch};
 use tokio_util::sync::CancellationToken;
 
 // 재스캔 스케줄 (500ms 루프 기준 실패 횟수)
@@ -22,52 +27,197 @@ use tokio_util::sync::CancellationToken;
 const RESCAN_SCHEDULE_COLD: &[u32] = &[10, 120, 240];
 const RESCAN_SCHEDULE_WARM: &[u32] = &[120, 240];
 
+// 재스캔을 모두 마친 뒤에도 로딩 상태가 아닌 실패가 이만큼(60초) 쌓이도록 한 번도 성공하지 못하면
+// 현재 설정이 동작하지 않는다고 보고한다.
+const CONFIG_EXHAUSTED_AFTER: u32 = 120;
+
+// 캐시된 트랜스폼 주소를 쓰더라도 이 주기마다 체인 전체를 다시 따라가 검증한다.
+// (폰 교체, 월드 원점 이동 등은 주소가 살아 있어도 값만 바뀔 수 있다)
+const CHAIN_REVALIDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+// 맵 이름은 자주 바뀌지 않으므로 위치 샘플마다 읽지 않는다.
+const MAP_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+// 샘플링 주기 (Hz). 실패 시에는 재스캔 스케줄이 500ms 기준이므로 항상 ERROR_INTERVAL로 돈다.
+pub const DEFAULT_MAX_SAMPLE_RATE: u32 = 30;
+const SAMPLE_RATE_LIMIT: u32 = 60;
+const IDLE_SAMPLE_RATE: u32 = 2;
+const ERROR_INTERVAL: Duration = Duration::from_millis(500);
+// 이 시간 동안 위치/시점 변화가 없으면 IDLE_SAMPLE_RATE로 낮춘다.
+const STILL_BACKOFF_AFTER: Duration = Duration::from_secs(1);
+const MOVE_EPSILON: f64 = 1.0; // cm
+const TURN_EPSILON: f32 = 0.5; // deg
+
+/// 수집 루프의 샘플링 주기를 정하는 입력값 (RtcSupervisor → collection_loop)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SampleRateControl {
+    /// 사용자 설정 최대 Hz
+    pub max_hz: u32,
+    /// 피어들이 요청한 Hz 중 최대값
+    pub requested_hz: Option<u32>,
+    pub peer_count: usize,
+}
+
+impl Default for SampleRateControl {
+    fn default() -> Self {
+        Self { max_hz: DEFAULT_MAX_SAMPLE_RATE, requested_hz: None, peer_count: 0 }
+    }
+}
+
+impl SampleRateControl {
+    pub fn set_max_hz(&mut self, max_hz: Option<u32>) {
+        self.max_hz = max_hz.unwrap_or(DEFAULT_MAX_SAMPLE_RATE).clamp(1, SAMPLE_RATE_LIMIT);
+    }
+
+    /// 움직이는 중일 때의 목표 Hz. 요청이 없으면 설정 최대값을 쓴다.
+    fn active_hz(&self) -> u32 {
+        self.requested_hz.unwrap_or(self.max_hz).clamp(1, self.max_hz)
+    }
+}
+
+/// 움직임/피어 수에 따라 샘플링 Hz를 정한다.
+#[derive(Default)]
+struct SamplePacer {
+    last: Option<PlayerInfo>,
+    still_since: Option<Instant>,
+}
+
+impl SamplePacer {
+    fn next_hz(&mut self, info: &PlayerInfo, control: &SampleRateControl) -> u32 {
+        let moved = self.last.as_ref().is_none_or(|last| has_moved(last, info));
+        self.last = Some(*info);
+        if moved {
+            self.still_since = None;
+        } else if self.still_since.is_none() {
+            self.still_since = Some(Instant::now());
+        }
+
+        let active = control.active_hz();
+        let still = self.still_since.is_some_and(|t| t.elapsed() >= STILL_BACKOFF_AFTER);
+        if control.peer_count == 0 || still {
+            active.min(IDLE_SAMPLE_RATE)
+        } else {
+            active
+        }
+    }
+}
+
+fn has_moved(a: &PlayerInfo, b: &PlayerInfo) -> bool {
+    let (dx, dy, dz) = (a.x - b.x, a.y - b.y, a.z - b.z);
+    (dx * dx + dy * dy + dz * dz).sqrt() > MOVE_EPSILON
+        || (a.yaw - b.yaw).abs() > TURN_EPSILON
+        || (a.pitch - b.pitch).abs() > TURN_EPSILON
+}
+
+/// 현재 적용된 원격 설정으로 플레이어를 찾은 적이 있는지
+#[derive(Clone, Copy, PartialEq)]
+enum ConfigHealth {
+    Untested,
+    Verified,
+    /// 모든 variant와 재스캔이 실패했다.
+    Exhausted,
+}
+
 /// OS별 게임 프로세스 래퍼
 pub struct NativeCollector {
     proc: PlatformProc,
     offset: Option<WuwaOffset>,
+    // (해석된 주소, 해석 시각) — offset이 있을 때만 유효
+    resolved: Option<(ResolvedPlayer, Instant)>,
     consecutive_failures: u32,
     rescan_stage: usize,
+    // 로딩 상태가 아닌 실패만 센 횟수 (설정 롤백 판단용, 재스캔과 별개)
+    config_failures: u32,
     cold_start: bool, // 초기 스캔 실패(ACE 미복호화) 여부
+    names: Option<FNameResolver>,
+    map_name: Option<String>,
+    last_map_check: Option<Instant>,
+    // 마지막으로 반영한 원격 설정 (게시된 Arc를 그대로 보관해 포인터로 비교한다)
+    applied_config: Option<Arc<TrackerConfig>>,
+    config_health: ConfigHealth,
+    health_reported: bool,
+    variant_stats: VariantStats,
 }
 
 impl NativeCollector {
     #[cfg(windows)]
-    pub fn from_win_proc(proc: crate::win_proc::WinProc) -> Self {
-        let cold_start = !proc.gworld_ready();
-        Self { proc, offset: None, consecutive_failures: 0, rescan_stage: 0, cold_start }
+    pub fn from_win_proc(proc: crate::win_proc::WinProc, cache_dir: &Path) -> Self {
+        Self::from_proc(proc, cache_dir)
     }
 
-    pub async fn new(proc_name: &str, cache_dir: PathBuf, scan_config: Option<GWorldScanConfig>) -> Result<Self> {
+    pub async fn new(
+        proc_name: &str,
+        cache_dir: PathBuf,
+        scan_config: Option<GWorldScanConfig>,
+        variants: Vec<WuwaOffset>,
+    ) -> Result<Self> {
         let proc_name = proc_name.to_string();
-        let proc =
-            tokio::task::spawn_blocking(move || PlatformProc::new(&proc_name, cache_dir, scan_config)).await??;
+        let proc_cache_dir = cache_dir.clone();
+        let proc = tokio::task::spawn_blocking(move || {
+            PlatformProc::new(&proc_name, proc_cache_dir, scan_config, &variants)
+        })
+        .await??;
+        Ok(Self::from_proc(proc, &cache_dir))
+    }
+
+    fn from_proc(proc: PlatformProc, cache_dir: &Path) -> Self {
         let cold_start = !proc.gworld_ready();
-        Ok(Self { proc, offset: None, consecutive_failures: 0, rescan_stage: 0, cold_start })
+        let variant_stats = VariantStats::load(cache_dir, proc.build_key());
+        Self {
+            proc,
+            offset: None,
+            resolved: None,
+            consecutive_failures: 0,
+            rescan_stage: 0,
+            config_failures: 0,
+            cold_start,
+            names: None,
+            map_name: None,
+            last_map_check: None,
+            applied_config: None,
+            config_health: ConfigHealth::Untested,
+            health_reported: false,
+            variant_stats,
+        }
     }
 
     fn get_location(
         &mut self,
-        available_offsets: &Option<Vec<WuwaOffset>>,
-    ) -> Result<crate::types::PlayerInfo, NativeError> {
+        available_offsets: Option<&[WuwaOffset]>,
+    ) -> Result<PlayerInfo, NativeError> {
         let Some(variants) = available_offsets else {
-            return Err(PointerChainError {
-                message: "오프셋 데이터를 불러오는 중입니다...".to_string(),
-            });
+            return Err(NativeError::OffsetsNotLoaded);
         };
 
-        match select_player_info(&self.proc, &mut self.offset, variants) {
-            Ok(info) => {
+        if let Some(info) = self.read_cached_location() {
+            return Ok(info);
+        }
+
+        match select_player_info(&self.proc, &mut self.offset, variants, Some(&mut self.variant_stats)) {
+            Ok((info, resolved)) => {
                 self.consecutive_failures = 0;
+                self.config_failures = 0;
+                self.resolved = Some((resolved, Instant::now()));
+                self.set_config_health(ConfigHealth::Verified);
                 Ok(info)
             }
             Err(e) => {
                 self.consecutive_failures += 1;
+                // 타이틀 화면/로딩 화면의 NULL 체인은 정상 설정에서도 계속 나므로 롤백 근거로 세지 않는다.
+                if !e.is_loading_state() {
+                    self.config_failures += 1;
+                }
                 let schedule = if self.cold_start { RESCAN_SCHEDULE_COLD } else { RESCAN_SCHEDULE_WARM };
+                if self.config_health == ConfigHealth::Untested
+                    && self.rescan_stage >= schedule.len()
+                    && self.config_failures >= CONFIG_EXHAUSTED_AFTER
+                {
+                    self.set_config_health(ConfigHealth::Exhausted);
+                }
                 if let Some(&threshold) = schedule.get(self.rescan_stage) {
                     if self.consecutive_failures >= threshold {
                         log::info!("{}회 연속 실패 → GWorld 재스캔 (시도 {})", threshold, self.rescan_stage + 1);
-                        self.proc.rescan_gworld();
+                        self.proc.rescan_gworld(variants);
                         self.rescan_stage += 1;
                         self.consecutive_failures = 0;
                         self.offset = None;
@@ -78,26 +228,152 @@ impl NativeCollector {
         }
     }
 
+    /// 재검증 주기 안이면 캐시된 주소에서 트랜스폼/월드 원점만 한 번에 읽는다. (레벨이 바뀌었으면 실패)
+    /// 실패하면 캐시를 버리고 None을 반환해 전체 체인 해석으로 넘긴다. (프로세스 종료 판정 포함)
+    fn read_cached_location(&mut self) -> Option<PlayerInfo> {
+        self.offset.as_ref()?;
+        let (resolved, resolved_at) = self.resolved.as_ref()?;
+        if resolved_at.elapsed() >= CHAIN_REVALIDATE_INTERVAL {
+            return None;
+        }
+        match read_resolved_player(&self.proc, resolved) {
+            Ok(info) => Some(info),
+            Err(e) => {
+                log::debug!("캐시된 체인 주소 무효, 재해석: {}", e);
+                self.resolved = None;
+                None
+            }
+        }
+    }
+
+    /// 맵 이름이 바뀌었으면 새 이름을 반환한다. (MAP_CHECK_INTERVAL마다 한 번 확인)
+    fn poll_map_change(&mut self, names: Option<&NameConfig>) -> Option<String> {
+        let (Some(config), Some(offset)) = (names, self.offset.as_ref()) else {
+            return None;
+        };
+        if self.last_map_check.is_some_and(|t| t.elapsed() < MAP_CHECK_INTERVAL) {
+            return None;
+        }
+        self.last_map_check = Some(Instant::now());
+
+        if self.names.as_ref().map(|r| r.config()) != Some(config) {
+            self.names = Some(FNameResolver::new(config.clone()));
+        }
+        let resolver = self.names.as_mut()?;
+        match resolver.read_map_name(&self.proc, offset) {
+            Ok(name) => {
+                if self.map_name.as_deref() == Some(name.as_str()) {
+                    return None;
+                }
+                log::info!("맵 변경: {} → {}", self.map_name.as_deref().unwrap_or("-"), name);
+                self.map_name = Some(name.clone());
+                Some(name)
+            }
+            Err(e) => {
+                log::debug!("맵 이름 읽기 실패: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 원격 설정이 교체되었으면 반영한다.
+    /// 선택된 variant와 캐시된 주소를 버리고, GWorld 스캔 설정이 바뀌었으면 다시 스캔한다.
+    /// publish는 내용이 바뀔 때만 새 Arc를 게시하므로 매 틱 포인터 비교만 한다.
+    fn sync_config(&mut self, config: &Arc<TrackerConfig>) {
+        if self.applied_config.as_ref().is_some_and(|c| Arc::ptr_eq(c, config)) {
+            return;
+        }
+        self.offset = None;
+        self.resolved = None;
+        self.consecutive_failures = 0;
+        self.rescan_stage = 0;
+        self.config_failures = 0;
+        self.config_health = ConfigHealth::Untested;
+        self.health_reported = false;
+        if self.proc.set_scan_config(config.gworld_scan.clone()) {
+            log::info!("GWorld 스캔 설정 변경 → 재스캔");
+            self.proc.rescan_gworld(&config.offsets);
+        }
+        self.applied_config = Some(Arc::clone(config));
+    }
+
+    fn set_config_health(&mut self, health: ConfigHealth) {
+        if self.config_health != health {
+            self.config_health = health;
+            self.health_reported = false;
+        }
+    }
+
+    /// 현재 설정이 처음 성공했거나 완전히 실패했으면 한 번만 보고한다.
+    fn take_config_report(&mut self) -> Option<CollectorMessage> {
+        if self.health_reported {
+            return None;
+        }
+        let last_updated = self.applied_config.as_ref()?.last_updated.clone();
+        let message = match self.config_health {
+            ConfigHealth::Untested => return None,
+            ConfigHealth::Verified => CollectorMessage::ConfigVerified(last_updated),
+            ConfigHealth::Exhausted => CollectorMessage::ConfigExhausted(last_updated),
+        };
+        self.health_reported = true;
+        Some(message)
+    }
+
+    /// 로컬 override가 적용된 경우 이름 뒤에 표시를 붙인다.
     fn get_active_offset_name(&self) -> Option<String> {
-        self.offset
-            .as_ref()
-            .map(|offset| self.proc.active_offset_name(offset))
+        let offset = self.offset.as_ref()?;
+        let name = self.proc.active_offset_name(offset);
+        let overridden = offset.local_override
+            || self
+                .applied_config
+                .as_ref()
+                .is_some_and(|c| c.gworld_scan.local_override);
+        Some(if overridden { format!("{} (override)", name) } else { name })
+    }
+
+    /// 현재 프로세스에서 select_player_info가 읽는 모든 영역을 스냅샷으로 기록한다.
+    /// 실패한 체인도 실패 직전까지 읽은 영역은 남으므로 그대로 재현할 수 있다.
+    pub fn record_snapshot(&self, variants: &[WuwaOffset]) -> MemorySnapshot {
+        let recorder = MemoryRecorder::new(&self.proc);
+        let mut offset = None;
+        match select_player_info(&recorder, &mut offset, variants, None) {
+            Ok(_) => log::info!("스냅샷 기록 완료 (성공한 체인 포함)"),
+            Err(e) => log::warn!("스냅샷 기록 완료 (체인 실패: {})", e),
+        }
+        recorder.into_snapshot()
+    }
+
+    /// 모든 variant의 체인을 한 번씩 따라가 단계별 주소/값을 기록한다. (수집 상태는 바꾸지 않음)
+    pub fn diagnose_chains(&self, variants: &[WuwaOffset]) -> ChainReport {
+        diagnostics::diagnose_player_chains(&self.proc, variants, self.get_active_offset_name())
     }
 }
 
+/// 기록된 스냅샷에 대해 select_player_info를 그대로 재실행한다.
+pub fn replay_snapshot(path: &Path, variants: &[WuwaOffset]) -> Result<PlayerInfo, String> {
+    let proc = SnapshotProc::load(path).map_err(|e| e.to_string())?;
+    let mut offset = None;
+    select_player_info(&proc, &mut offset, variants, None)
+        .map(|(info, _)| info)
+        .map_err(|e| e.to_string())
+}
+
 pub async fn collection_loop(
     collector_arc: Arc<Mutex<Option<NativeCollector>>>,
     pm_tx: mpsc::Sender<CollectorMessage>,
     cancel: CancellationToken,
-    offsets_arc: Arc<Mutex<Option<TrackerConfig>>>,
+    offsets_arc: Arc<Mutex<Option<Arc<TrackerConfig>>>>,
+    sample_rate_rx: watch::Receiver<SampleRateControl>,
 ) {
     let mut reported_offset: Option<String> = None;
     let mut last_error_emit: Option<Instant> = None;
+    let mut pacer = SamplePacer::default();
+    let mut reported_hz: Option<u32> = None;
     loop {
-        let offsets_snapshot: Option<Vec<WuwaOffset>> = offsets_arc.lock().await
-            .as_ref()
-            .map(|c| c.offsets.clone());
-        let result = {
+        let tick_start = Instant::now();
+        let mut interval = ERROR_INTERVAL;
+        let config_snapshot: Option<Arc<TrackerConfig>> = offsets_arc.lock().await.clone();
+        let (result, config_report) = {
             // 1. 상태 관리자를 잠그고 공유 상태에 접근합니다.
             let mut collector_opt_guard = collector_arc.lock().await;
 
@@ -108,12 +384,19 @@ pub async fn collection_loop(
                 break;
             };
 
+            // 설정이 교체되었으면 수집기에 반영합니다.
+            if let Some(config) = &config_snapshot {
+                collector.sync_config(config);
+            }
+
             // 3. get_location을 호출하고 결과를 매칭합니다.
-            match collector.get_location(&offsets_snapshot) {
+            let result = match collector.get_location(config_snapshot.as_ref().map(|c| c.offsets.as_slice())) {
                 // 성공 시 데이터 전송
                 Ok(loc) => {
                     let offset_name = collector.get_active_offset_name();
-                    Ok((loc, offset_name))
+                    let map_change =
+                        collector.poll_map_change(config_snapshot.as_ref().and_then(|c| c.names.as_ref()));
+                    Ok((loc, offset_name, map_change))
                 }
 
                 // '프로세스 종료'는 치명적 오류
@@ -121,11 +404,20 @@ pub async fn collection_loop(
 
                 // 그 외 모든 오류는 일시적인 것으로 간주
                 Err(e) => Err(e),
-            }
+            };
+            (result, collector.take_config_report())
         };
 
+        // 설정 검증 결과는 RtcSupervisor를 거쳐 오프셋 기록(롤백 판단)에 전달됩니다.
+        if let Some(report) = config_report
+            && pm_tx.send(report).await.is_err()
+        {
+            log::info!("Collection loop exiting: no receiver");
+            break;
+        }
+
         match result {
-            Ok((loc, offset_name)) => {
+            Ok((loc, offset_name, map_change)) => {
                 last_error_emit = None;
                 if let Some(name) = offset_name {
                     if reported_offset.as_deref() != Some(name.as_str()) {
@@ -141,6 +433,22 @@ pub async fn collection_loop(
                         reported_offset = Some(name);
                     }
                 }
+                if let Some(map_name) = map_change
+                    && pm_tx.send(CollectorMessage::MapChanged(map_name)).await.is_err()
+                {
+                    log::info!("Collection loop exiting: no receiver");
+                    break;
+                }
+                let control = *sample_rate_rx.borrow();
+                let hz = pacer.next_hz(&loc, &control);
+                interval = Duration::from_secs(1) / hz;
+                if reported_hz != Some(hz) {
+                    if pm_tx.send(CollectorMessage::SampleRateChanged(hz)).await.is_err() {
+                        log::info!("Collection loop exiting: no receiver");
+                        break;
+                    }
+                    reported_hz = Some(hz);
+                }
                 if pm_tx.send(CollectorMessage::Data(loc)).await.is_err() {
                     log::info!("Collection loop exiting: no receiver");
                     break;
@@ -162,7 +470,7 @@ pub async fn collection_loop(
                     last_error_emit = Some(Instant::now());
                     log::warn!("collect: {}", e);
                     if pm_tx
-                        .send(CollectorMessage::TemporalError(e.user_message().to_string()))
+                        .send(CollectorMessage::TemporalError(TrackerError::from(&e)))
                         .await
                         .is_err()
                     {
@@ -178,7 +486,7 @@ pub async fn collection_loop(
                 log::info!("Collection loop exiting: exit signal received");
                 break;
             }
-            _ = tokio::time::sleep(Duration::from_millis(500)) => {}
+            _ = tokio::time::sleep(interval.saturating_sub(tick_start.elapsed())) => {}
         }
     }
 }
@@ -0,0 +1,124 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::offset_manager::signature_path;
+use crate::util::write_atomic;
+
+const HISTORY_DIR: &str = "offsets_history";
+const INDEX_FILE: &str = "index.json";
+/// 보관할 서로 다른 설정 수 (known-good은 이 수와 별개로 항상 남긴다)
+const MAX_ENTRIES: usize = 5;
+
+/// 서명된 오프셋 문서를 last_updated 단위로 보관하고,
+/// 실제로 select_player_info에 성공한 마지막 설정(known-good)을 기록한다.
+pub struct ConfigHistory {
+    dir: PathBuf,
+    index: HistoryIndex,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct HistoryIndex {
+    /// 최신순
+    entries: Vec<HistoryEntry>,
+    #[serde(default)]
+    known_good: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct HistoryEntry {
+    last_updated: String,
+    file: String,
+}
+
+impl ConfigHistory {
+    pub fn open(config_dir: &Path) -> Self {
+        let dir = config_dir.join(HISTORY_DIR);
+        let index = fs::read_to_string(dir.join(INDEX_FILE))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        Self { dir, index }
+    }
+
+    pub fn known_good(&self) -> Option<&str> {
+        self.index.known_good.as_deref()
+    }
+
+    pub fn contains(&self, last_updated: &str) -> bool {
+        self.index.entries.iter().any(|e| e.last_updated == last_updated)
+    }
+
+    /// 서명 검증을 통과한 원문을 그대로 저장한다. 같은 last_updated는 최신 항목으로 올린다.
+    pub fn record(&mut self, last_updated: &str, data: &[u8], signature: &str) -> Result<()> {
+        fs::create_dir_all(&self.dir).context("오프셋 기록 폴더 생성 실패")?;
+        let file = file_name(last_updated);
+        let path = self.dir.join(&file);
+        // 색인은 두 파일이 모두 쓰인 뒤에 갱신하므로, 도중에 종료되어도 색인이 깨진 항목을 가리키지 않는다.
+        write_atomic(&path, data).context("오프셋 기록 저장 실패")?;
+        write_atomic(&signature_path(&path), signature.as_bytes()).context("오프셋 기록 서명 저장 실패")?;
+
+        self.index.entries.retain(|e| e.last_updated != last_updated && e.file != file);
+        self.index.entries.insert(0, HistoryEntry { last_updated: last_updated.to_string(), file });
+        self.prune();
+        self.save()
+    }
+
+    pub fn mark_known_good(&mut self, last_updated: &str) -> Result<()> {
+        if self.known_good() == Some(last_updated) {
+            return Ok(());
+        }
+        if !self.contains(last_updated) {
+            log::debug!("기록에 없는 오프셋 설정은 known-good으로 표시하지 않습니다: {}", last_updated);
+            return Ok(());
+        }
+        log::info!("known-good 오프셋 설정: {}", last_updated);
+        self.index.known_good = Some(last_updated.to_string());
+        self.save()
+    }
+
+    /// 저장된 원문과 서명. 검증은 호출하는 쪽에서 한다.
+    pub fn load(&self, last_updated: &str) -> Result<(Vec<u8>, String)> {
+        let entry = self
+            .index
+            .entries
+            .iter()
+            .find(|e| e.last_updated == last_updated)
+            .with_context(|| format!("기록에 없는 오프셋 설정: {}", last_updated))?;
+        let path = self.dir.join(&entry.file);
+        let data = fs::read(&path).context("오프셋 기록을 읽지 못했습니다.")?;
+        let signature = fs::read_to_string(signature_path(&path)).context("오프셋 기록 서명이 없습니다.")?;
+        Ok((data, signature))
+    }
+
+    /// MAX_ENTRIES를 넘는 오래된 항목을 지운다. known-good은 남긴다.
+    fn prune(&mut self) {
+        let known_good = self.index.known_good.clone();
+        let mut kept = 0;
+        self.index.entries.retain(|e| {
+            if kept < MAX_ENTRIES || known_good.as_deref() == Some(e.last_updated.as_str()) {
+                kept += 1;
+                return true;
+            }
+            let path = self.dir.join(&e.file);
+            let _ = fs::remove_file(signature_path(&path));
+            let _ = fs::remove_file(path);
+            false
+        });
+    }
+
+    fn save(&self) -> Result<()> {
+        let data = serde_json::to_string(&self.index)?;
+        write_atomic(&self.dir.join(INDEX_FILE), data.as_bytes()).context("오프셋 기록 색인 저장 실패")
+    }
+}
+
+fn file_name(last_updated: &str) -> String {
+    let stem: String = last_updated
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    format!("{}.json", stem)
+}
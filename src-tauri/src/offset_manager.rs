@@ -1,100 +1,755 @@
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use crate::offsets::TrackerConfig;
-use anyhow::{Result, Context, anyhow};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::offset_history::ConfigHistory;
+use crate::offsets::{TrackerConfig, TrackerConfigOverride};
+use crate::types::LocalStorageConfig;
+use crate::util;
+use anyhow::{Result, Context, anyhow, bail};
+use minisign_verify::{PublicKey, Signature};
+use reqwest::{StatusCode, Url};
+use reqwest::header::{ETAG, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use serde::Serialize;
 use tauri::{Emitter, Manager};
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Notify};
 
 const CACHE_FILE: &str = "offsets_cache_v2.json";
+const SIGNATURE_EXT: &str = ".minisig";
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30 * 60);
+const OVERRIDE_FILE: &str = "offsets_override.json";
+const OVERRIDE_POLL_INTERVAL: Duration = Duration::from_secs(2);
 
-fn get_remote_urls() -> Vec<&'static str> {
+/// 오프셋 JSON 서명 검증용 minisign 공개키 (key id 428A067978740CDF, key/offsets.key.pub).
+/// 업데이터 키와 분리해 두어, 오프셋 서명 키가 새어도 앱 업데이트는 위조할 수 없다.
+const OFFSETS_PUBLIC_KEY: &str = "RWTfDHR4eQaKQmG0a5KyBPqWh5rl+6UueMzlAzq7sfQ17JqeRVxeJhhg";
+
+/// LocalStorageConfig.offset_sources가 없을 때 사용하는 기본 소스
+fn default_sources() -> Vec<String> {
     let mut urls = vec![
-        "https://wuwa.moe/tracker-offsets-v2.json",
-        "https://raw.githubusercontent.com/wuwamoe/wuwa-moe/refs/heads/main/static/tracker-offsets-v2.json",
+        "https://wuwa.moe/tracker-offsets-v2.json".to_string(),
+        "https://raw.githubusercontent.com/wuwamoe/wuwa-moe/refs/heads/main/static/tracker-offsets-v2.json".to_string(),
     ];
 
     #[cfg(debug_assertions)]
     {
-        urls.insert(0, "http://localhost:1420/tracker-offsets-v2.json");
+        urls.insert(0, "http://localhost:1420/tracker-offsets-v2.json".to_string());
     }
 
     urls
 }
 
-pub async fn start_offset_loading(app_handle: tauri::AppHandle, target: Arc<Mutex<Option<TrackerConfig>>>) {
-    match load_offsets(&app_handle).await {
+/// 오프셋 소스 하나. 어느 쪽이든 `{source}.minisig` 서명이 함께 있어야 한다.
+enum Source {
+    Http(Url),
+    File(PathBuf),
+}
+
+impl Source {
+    /// https URL 또는 file:// 경로만 허용한다. (디버그 빌드는 로컬 개발 서버용 http도 허용)
+    fn parse(source: &str) -> Result<Self> {
+        let url = Url::parse(source).context("잘못된 소스 주소")?;
+        match url.scheme() {
+            "https" => Ok(Self::Http(url)),
+            "http" if cfg!(debug_assertions) => Ok(Self::Http(url)),
+            "file" => url
+                .to_file_path()
+                .map(Self::File)
+                .map_err(|_| anyhow!("잘못된 파일 경로: {}", source)),
+            scheme => bail!("지원하지 않는 소스 형식입니다: {} (https 또는 file://)", scheme),
+        }
+    }
+}
+
+/// 사용자가 입력한 소스 목록을 저장하기 전에 검사한다.
+pub fn validate_sources(sources: &[String]) -> Result<()> {
+    for source in sources {
+        Source::parse(source).with_context(|| format!("'{}'", source))?;
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SourceResult {
+    /// 서명이 검증된 새 문서를 받았다.
+    Fetched,
+    /// 304 (마지막으로 받은 문서와 같음)
+    NotModified,
+    Failed,
+    /// 앞선 소스에서 이미 받았거나 오프라인 모드라 시도하지 않았다.
+    Skipped,
+}
+
+/// 소스별 마지막 시도 결과 (문제 해결용)
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceFetchResult {
+    pub source: String,
+    pub result: SourceResult,
+    pub message: Option<String>,
+    pub last_updated: Option<String>,
+    /// 시도 시각 (unix seconds)
+    pub checked_at: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OffsetSourceStatus {
+    pub offline_mode: bool,
+    pub sources: Vec<SourceFetchResult>,
+    /// 현재 원격 설정을 가져온 곳. 소스 주소 또는 "cache"
+    pub loaded_from: Option<String>,
+}
+
+/// 수집기가 보고한 설정 검증 결과 (last_updated)
+enum ConfigReport {
+    Verified(String),
+    Exhausted(String),
+}
+
+/// 오프셋 소스 상태, 즉시 갱신 요청, 설정 검증 결과. TauriState와 오프셋 감시 태스크가 공유한다.
+#[derive(Default)]
+pub struct OffsetSources {
+    pub status: Mutex<OffsetSourceStatus>,
+    refresh: Notify,
+    reports: Mutex<Vec<ConfigReport>>,
+    report_ready: Notify,
+}
+
+impl OffsetSources {
+    /// 소스 설정이 바뀌었을 때 호출한다. 감시 태스크가 설정을 다시 읽고 바로 가져온다.
+    pub fn request_refresh(&self) {
+        self.refresh.notify_one();
+    }
+
+    /// 이 설정으로 플레이어를 찾았다. known-good으로 기록된다.
+    pub async fn report_verified(&self, last_updated: String) {
+        self.reports.lock().await.push(ConfigReport::Verified(last_updated));
+        self.report_ready.notify_one();
+    }
+
+    /// 이 설정은 모든 variant가 실패했다. 최신 설정이면 known-good으로 되돌린다.
+    pub async fn report_exhausted(&self, last_updated: String) {
+        self.reports.lock().await.push(ConfigReport::Exhausted(last_updated));
+        self.report_ready.notify_one();
+    }
+}
+
+pub async fn start_offset_loading(
+    app_handle: tauri::AppHandle,
+    target: Arc<Mutex<Option<Arc<TrackerConfig>>>>,
+    sources: Arc<OffsetSources>,
+) {
+    let settings = util::get_config(app_handle.clone()).await.unwrap_or_default();
+    let mut fetcher = OffsetFetcher::new(&settings, sources.clone());
+    let remote = match load_offsets(&app_handle, &mut fetcher).await {
         Ok(config) => {
             log::info!(
                 "오프셋 로드 완료 (last_updated: {}, 패턴 스캔: {})",
                 config.last_updated,
                 config.gworld_scan.enabled
             );
-            *target.lock().await = Some(config);
+            Some(config)
         }
         Err(_) => {
             let error_message = String::from("오프셋 로딩 실패! 인터넷 연결을 확인하고, 관리자에게 문의하세요.");
             if let Err(emit_err) = app_handle.emit("report-error-toast", error_message) {
                 log::error!("Failed to emit error to frontend: {}", emit_err);
             }
+            None
         }
-    }
+    };
+
+    watch_loop(&app_handle, &target, &mut fetcher, &sources, remote).await;
 }
 
-pub async fn load_offsets(app_handle: &tauri::AppHandle) -> Result<TrackerConfig> {
-    let cache_path = app_handle.path().app_config_dir()?
-        .join(CACHE_FILE);
+pub async fn load_offsets(app_handle: &tauri::AppHandle, fetcher: &mut OffsetFetcher) -> Result<TrackerConfig> {
+    let config_dir = app_handle.path().app_config_dir()?;
 
-    match fetch_from_remotes().await {
-        Ok(config) => {
-            let _ = save_cache(&cache_path, &config);
+    if fetcher.offline_mode {
+        log::info!("오프라인 모드: 로컬 캐시만 사용합니다.");
+        fetcher.record_offline().await;
+        return fetcher.load_cache(&config_dir).await;
+    }
+
+    match fetcher.fetch().await {
+        Ok(Some((config, signed))) => {
+            if let Err(e) = persist(&config_dir, &config, &signed) {
+                log::warn!("오프셋 캐시 저장 실패: {}", e);
+            }
             Ok(config)
         }
+        // 첫 요청에는 조건부 헤더가 없으므로 304를 받을 수 없다.
+        Ok(None) => fetcher.load_cache(&config_dir).await,
         Err(e) => {
             log::warn!("모든 서버 연결 실패, 로컬 캐시를 사용합니다. 에러: {}", e);
             let error_message = String::from("오프셋 동기화 실패");
             if let Err(emit_err) = app_handle.emit("report-error-toast", error_message) {
                 log::error!("Failed to emit error to frontend: {}", emit_err);
             }
-            load_cache(&cache_path)
+            fetcher.load_cache(&config_dir).await
         }
     }
 }
 
-async fn fetch_from_remotes() -> Result<TrackerConfig> {
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(5))
-        .build()?;
+/// 원격 오프셋(REFRESH_INTERVAL)과 로컬 override 파일(OVERRIDE_POLL_INTERVAL)을 감시하고,
+/// 둘 중 하나가 바뀌면 합친 설정으로 통째로 교체한다.
+/// 최신 원격 설정이 동작하지 않는다는 보고를 받으면 known-good 설정으로 되돌린다.
+/// 수집기는 다음 틱에 교체된 설정을 감지해 variant 선택/GWorld 스캔을 다시 한다.
+async fn watch_loop(
+    app_handle: &tauri::AppHandle,
+    target: &Mutex<Option<Arc<TrackerConfig>>>,
+    fetcher: &mut OffsetFetcher,
+    sources: &OffsetSources,
+    mut remote: Option<TrackerConfig>,
+) {
+    let override_path = app_handle
+        .path()
+        .app_config_dir()
+        .map(|dir| dir.join(OVERRIDE_FILE))
+        .ok();
+    let mut local = OverrideWatcher::default();
+    let mut refresh = tokio::time::interval_at(
+        tokio::time::Instant::now() + REFRESH_INTERVAL,
+        REFRESH_INTERVAL,
+    );
+    let mut poll = tokio::time::interval(OVERRIDE_POLL_INTERVAL);
+    let mut rollback = Rollback::default();
 
-    for url in get_remote_urls() {
-        log::info!("Trying to fetch offsets from: {}", url);
-        match client.get(url).send().await {
-            Ok(res) => {
-                if res.status().is_success() {
-                    match res.json::<TrackerConfig>().await {
-                        Ok(config) => return Ok(config),
-                        Err(e) => log::warn!("JSON 파싱 에러 ({}): {}", url, e),
+    loop {
+        // 첫 poll 틱은 즉시 끝나므로 시작 직후 override를 읽고 설정을 게시한다.
+        tokio::select! {
+            _ = refresh.tick() => {
+                if !refresh_remote(app_handle, fetcher, &mut remote).await {
+                    continue;
+                }
+                rollback = Rollback::default();
+            }
+            _ = sources.refresh.notified() => {
+                let settings = util::get_config(app_handle.clone()).await.unwrap_or_default();
+                fetcher.configure(&settings);
+                log::info!("오프셋 소스 설정 변경 (오프라인 모드: {})", fetcher.offline_mode);
+                if !refresh_remote(app_handle, fetcher, &mut remote).await {
+                    continue;
+                }
+                rollback = Rollback::default();
+            }
+            _ = sources.report_ready.notified() => {
+                let reports = std::mem::take(&mut *sources.reports.lock().await);
+                let mut changed = false;
+                for report in reports {
+                    changed |= rollback.handle(app_handle, report, remote.as_ref());
+                }
+                if !changed {
+                    continue;
+                }
+            }
+            _ = poll.tick() => {
+                let Some(path) = &override_path else { continue };
+                if !local.poll(app_handle, path) {
+                    continue;
+                }
+            }
+        }
+
+        let base = rollback.config.as_ref().or(remote.as_ref());
+        publish(app_handle, target, base, local.current.as_ref()).await;
+    }
+}
+
+/// 최신 원격 설정 대신 사용 중인 known-good 설정. 새 원격 설정을 받으면 초기화된다.
+#[derive(Default)]
+struct Rollback {
+    config: Option<TrackerConfig>,
+    // 같은 원격 설정에 대해 되돌리기는 한 번만 시도한다. (메뉴 화면 등에서 왕복하지 않도록)
+    attempted: bool,
+}
+
+impl Rollback {
+    /// 게시할 설정이 바뀌었으면 true를 반환한다.
+    fn handle(&mut self, app_handle: &tauri::AppHandle, report: ConfigReport, remote: Option<&TrackerConfig>) -> bool {
+        let Ok(config_dir) = app_handle.path().app_config_dir() else {
+            return false;
+        };
+        let mut history = ConfigHistory::open(&config_dir);
+        match report {
+            ConfigReport::Verified(last_updated) => {
+                if let Err(e) = history.mark_known_good(&last_updated) {
+                    log::warn!("known-good 기록 실패: {}", e);
+                }
+                false
+            }
+            ConfigReport::Exhausted(last_updated) => {
+                if let Some(fallback) = &self.config
+                    && fallback.last_updated == last_updated
+                {
+                    log::warn!("known-good 설정 {}도 실패하여 최신 설정으로 돌아갑니다.", last_updated);
+                    self.config = None;
+                    return true;
+                }
+                let Some(remote) = remote.filter(|c| c.last_updated == last_updated) else {
+                    return false;
+                };
+                if self.attempted {
+                    return false;
+                }
+                self.attempted = true;
+                let Some(known_good) = history.known_good().filter(|k| *k != remote.last_updated) else {
+                    log::warn!("되돌릴 known-good 오프셋 설정이 없습니다.");
+                    return false;
+                };
+                let config = match history
+                    .load(known_good)
+                    .and_then(|(data, signature)| SignedDocument::verify(data, signature))
+                {
+                    Ok((config, _)) => config,
+                    Err(e) => {
+                        log::warn!("known-good 설정 {} 불러오기 실패: {:#}", known_good, e);
+                        return false;
                     }
-                } else {
-                    log::warn!("서버 응답 에러 ({}): {}", url, res.status());
+                };
+                log::warn!(
+                    "최신 오프셋 {}이 동작하지 않아 known-good {}으로 되돌립니다.",
+                    remote.last_updated,
+                    config.last_updated
+                );
+                let message = format!(
+                    "최신 오프셋이 동작하지 않아 이전 오프셋({})으로 되돌렸습니다.",
+                    config.last_updated
+                );
+                if let Err(emit_err) = app_handle.emit("report-error-toast", message) {
+                    log::error!("Failed to emit error to frontend: {}", emit_err);
                 }
+                self.config = Some(config);
+                true
             }
-            Err(e) => log::warn!("네트워크 연결 실패 ({}): {}", url, e),
         }
     }
+}
+
+/// 원격 소스를 다시 확인한다. 새 설정을 받았으면 캐시에 저장하고 true를 반환한다.
+async fn refresh_remote(
+    app_handle: &tauri::AppHandle,
+    fetcher: &mut OffsetFetcher,
+    remote: &mut Option<TrackerConfig>,
+) -> bool {
+    if fetcher.offline_mode {
+        fetcher.record_offline().await;
+        return false;
+    }
+    let (config, signed) = match fetcher.fetch().await {
+        Ok(Some(document)) => document,
+        Ok(None) => {
+            log::debug!("오프셋 변경 없음 (304)");
+            return false;
+        }
+        Err(e) => {
+            log::warn!("오프셋 갱신 실패, 기존 설정 유지: {}", e);
+            return false;
+        }
+    };
+    if remote.as_ref() == Some(&config) {
+        return false;
+    }
+    let persisted = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(anyhow::Error::from)
+        .and_then(|dir| persist(&dir, &config, &signed));
+    if let Err(e) = persisted {
+        log::warn!("오프셋 캐시 저장 실패: {}", e);
+    }
+    *remote = Some(config);
+    true
+}
+
+/// 원격 설정에 override를 합쳐 공유 설정을 교체한다. 처음 게시할 때는 프론트엔드에 알리지 않는다.
+async fn publish(
+    app_handle: &tauri::AppHandle,
+    target: &Mutex<Option<Arc<TrackerConfig>>>,
+    remote: Option<&TrackerConfig>,
+    local: Option<&TrackerConfigOverride>,
+) {
+    let merged = remote.map(|config| match local {
+        Some(local) => config.clone().with_override(local),
+        None => config.clone(),
+    });
+
+    let mut current = target.lock().await;
+    if current.as_deref() == merged.as_ref() {
+        return;
+    }
+    let Some(config) = merged.map(Arc::new) else {
+        return;
+    };
+    let previous = current.replace(Arc::clone(&config));
+    drop(current);
+
+    let Some(previous) = previous else {
+        return;
+    };
+    log::info!(
+        "새 오프셋 적용 (last_updated: {} → {}, override: {})",
+        previous.last_updated,
+        config.last_updated,
+        local.is_some()
+    );
+    if let Err(emit_err) = app_handle.emit("offsets-updated", config.last_updated.clone()) {
+        log::error!("Failed to emit offsets update to frontend: {}", emit_err);
+    }
+}
 
-    Err(anyhow!("모든 원격 저장소로부터 데이터를 가져오지 못했습니다."))
+/// offsets_override.json의 수정 시각을 기억해 바뀌었을 때만 다시 읽는다.
+#[derive(Default)]
+struct OverrideWatcher {
+    modified: Option<SystemTime>,
+    current: Option<TrackerConfigOverride>,
 }
 
-fn save_cache(path: &PathBuf, config: &TrackerConfig) -> Result<()> {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
+impl OverrideWatcher {
+    /// 파일이 생기거나, 바뀌거나, 지워졌으면 true를 반환한다.
+    /// 파싱에 실패하면 override를 끄고 사용자에게 알린다.
+    fn poll(&mut self, app_handle: &tauri::AppHandle, path: &Path) -> bool {
+        let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+        if modified == self.modified {
+            return false;
+        }
+        self.modified = modified;
+
+        let previous = self.current.take();
+        if modified.is_some() {
+            match load_override(path) {
+                Ok(local) => {
+                    log::info!(
+                        "오프셋 override 적용: variant {}개 ({})",
+                        local.offsets.len(),
+                        path.display()
+                    );
+                    self.current = Some(local);
+                }
+                Err(e) => {
+                    log::warn!("오프셋 override 파일 오류: {:#}", e);
+                    let error_message = format!("오프셋 override 파일 오류: {:#}", e);
+                    if let Err(emit_err) = app_handle.emit("report-error-toast", error_message) {
+                        log::error!("Failed to emit error to frontend: {}", emit_err);
+                    }
+                }
+            }
+        } else if previous.is_some() {
+            log::info!("오프셋 override 파일이 삭제되어 원격 설정만 사용합니다.");
+        }
+        previous != self.current
     }
-    let json = serde_json::to_string(config)?;
-    fs::write(path, json).context("캐시 저장 실패")
 }
 
-fn load_cache(path: &PathBuf) -> Result<TrackerConfig> {
-    let data = fs::read_to_string(path).context("저장된 캐시가 없습니다.")?;
-    Ok(serde_json::from_str(&data)?)
+fn load_override(path: &Path) -> Result<TrackerConfigOverride> {
+    let data = fs::read_to_string(path).context("override 파일을 읽지 못했습니다.")?;
+    serde_json::from_str(&data).context("JSON 파싱 에러")
+}
+
+/// 서명 검증을 통과한 오프셋 JSON 원문과 minisign 서명
+pub struct SignedDocument {
+    data: Vec<u8>,
+    signature: String,
+}
+
+impl SignedDocument {
+    /// 서명을 검증한 뒤 파싱한다. 서명은 원문 바이트 기준이므로 재직렬화하지 않는다.
+    fn verify(data: Vec<u8>, signature: String) -> Result<(TrackerConfig, Self)> {
+        verify_signature(&data, &signature)?;
+        let config = serde_json::from_slice(&data).context("JSON 파싱 에러")?;
+        Ok((config, Self { data, signature }))
+    }
+}
+
+fn verify_signature(data: &[u8], signature: &str) -> Result<()> {
+    let public_key = PublicKey::from_base64(OFFSETS_PUBLIC_KEY)
+        .map_err(|e| anyhow!("내장 공개키 디코딩 실패: {}", e))?;
+    let signature =
+        Signature::decode(signature).map_err(|e| anyhow!("서명 형식 오류: {}", e))?;
+    public_key
+        .verify(data, &signature, false)
+        .map_err(|e| anyhow!("서명 검증 실패: {}", e))
+}
+
+/// URL별 조건부 요청 검증자 (ETag / Last-Modified)
+#[derive(Default)]
+struct Validators {
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+}
+
+/// 오프셋 소스를 순서대로 시도하는 클라이언트. 서명 검증까지 통과한 응답의 검증자만 기억한다.
+pub struct OffsetFetcher {
+    client: Option<reqwest::Client>,
+    sources: Vec<String>,
+    offline_mode: bool,
+    validators: HashMap<String, Validators>,
+    status: Arc<OffsetSources>,
+}
+
+impl OffsetFetcher {
+    pub fn new(settings: &LocalStorageConfig, status: Arc<OffsetSources>) -> Self {
+        let mut fetcher = Self {
+            client: None,
+            sources: Vec::new(),
+            offline_mode: false,
+            validators: HashMap::new(),
+            status,
+        };
+        fetcher.configure(settings);
+        fetcher
+    }
+
+    /// 소스 목록이 비어 있으면 기본 소스를 사용한다.
+    fn configure(&mut self, settings: &LocalStorageConfig) {
+        self.sources = settings
+            .offset_sources
+            .clone()
+            .filter(|sources| !sources.is_empty())
+            .unwrap_or_else(default_sources);
+        self.offline_mode = settings.offline_mode.unwrap_or(false);
+    }
+
+    fn client(&mut self) -> Result<reqwest::Client> {
+        if let Some(client) = &self.client {
+            return Ok(client.clone());
+        }
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()?;
+        self.client = Some(client.clone());
+        Ok(client)
+    }
+
+    /// 서명이 검증된 새 문서를 반환한다. 조건부 요청에 304가 돌아오면 None (마지막으로 받은 문서와 같음)
+    pub async fn fetch(&mut self) -> Result<Option<(TrackerConfig, SignedDocument)>> {
+        let client = self.client()?;
+        let mut results = Vec::with_capacity(self.sources.len());
+        let mut fetched = None;
+
+        for source in self.sources.clone() {
+            if fetched.is_some() {
+                results.push(source_result(source, SourceResult::Skipped, None, None));
+                continue;
+            }
+            log::info!("Trying to fetch offsets from: {}", source);
+            match self.fetch_source(&client, &source).await {
+                Ok(Some((config, signed))) => {
+                    let last_updated = Some(config.last_updated.clone());
+                    results.push(source_result(source.clone(), SourceResult::Fetched, None, last_updated));
+                    self.status.status.lock().await.loaded_from = Some(source);
+                    fetched = Some(Some((config, signed)));
+                }
+                Ok(None) => {
+                    results.push(source_result(source, SourceResult::NotModified, None, None));
+                    fetched = Some(None);
+                }
+                Err(e) => {
+                    log::warn!("오프셋 가져오기 실패 ({}): {:#}", source, e);
+                    results.push(source_result(source, SourceResult::Failed, Some(format!("{:#}", e)), None));
+                }
+            }
+        }
+
+        let mut status = self.status.status.lock().await;
+        status.offline_mode = false;
+        status.sources = results;
+        drop(status);
+
+        fetched.ok_or_else(|| anyhow!("모든 원격 저장소로부터 데이터를 가져오지 못했습니다."))
+    }
+
+    async fn fetch_source(
+        &mut self,
+        client: &reqwest::Client,
+        source: &str,
+    ) -> Result<Option<(TrackerConfig, SignedDocument)>> {
+        match Source::parse(source)? {
+            Source::Http(url) => self.fetch_signed(client, url).await,
+            Source::File(path) => read_signed_file(&path).map(Some),
+        }
+    }
+
+    /// `{url}` 본문과 `{url}.minisig` 서명을 함께 받아 검증한다.
+    async fn fetch_signed(
+        &mut self,
+        client: &reqwest::Client,
+        url: Url,
+    ) -> Result<Option<(TrackerConfig, SignedDocument)>> {
+        let key = url.to_string();
+        let mut request = client.get(url.clone());
+        if let Some(validators) = self.validators.get(&key) {
+            if let Some(etag) = &validators.etag {
+                request = request.header(IF_NONE_MATCH, etag.clone());
+            }
+            if let Some(last_modified) = &validators.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified.clone());
+            }
+        }
+        let res = request.send().await.context("네트워크 연결 실패")?;
+        if res.status() == StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        if !res.status().is_success() {
+            return Err(anyhow!("서버 응답 에러: {}", res.status()));
+        }
+        let validators = Validators {
+            etag: res.headers().get(ETAG).cloned(),
+            last_modified: res.headers().get(LAST_MODIFIED).cloned(),
+        };
+        let data = res.bytes().await?.to_vec();
+
+        let signature_url = format!("{}{}", key, SIGNATURE_EXT);
+        let signature = fetch_bytes(client, &signature_url)
+            .await
+            .context("서명 파일을 가져오지 못했습니다.")?;
+        let signature = String::from_utf8(signature).context("서명 형식 오류")?;
+        let document = SignedDocument::verify(data, signature)?;
+        self.validators.insert(key, validators);
+        Ok(Some(document))
+    }
+
+    async fn load_cache(&self, config_dir: &Path) -> Result<TrackerConfig> {
+        let config = load_cache(config_dir)?;
+        self.status.status.lock().await.loaded_from = Some("cache".to_string());
+        Ok(config)
+    }
+
+    async fn record_offline(&self) {
+        let checked_at = unix_now();
+        let mut status = self.status.status.lock().await;
+        status.offline_mode = true;
+        status.sources = self
+            .sources
+            .iter()
+            .map(|source| SourceFetchResult {
+                source: source.clone(),
+                result: SourceResult::Skipped,
+                message: Some("오프라인 모드".to_string()),
+                last_updated: None,
+                checked_at,
+            })
+            .collect();
+    }
+}
+
+fn source_result(
+    source: String,
+    result: SourceResult,
+    message: Option<String>,
+    last_updated: Option<String>,
+) -> SourceFetchResult {
+    SourceFetchResult { source, result, message, last_updated, checked_at: unix_now() }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+async fn fetch_bytes(client: &reqwest::Client, url: &str) -> Result<Vec<u8>> {
+    let res = client
+        .get(url)
+        .send()
+        .await
+        .context("네트워크 연결 실패")?;
+    if !res.status().is_success() {
+        return Err(anyhow!("서버 응답 에러: {}", res.status()));
+    }
+    Ok(res.bytes().await?.to_vec())
+}
+
+pub fn signature_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(SIGNATURE_EXT);
+    PathBuf::from(name)
+}
+
+fn save_cache(path: &Path, document: &SignedDocument) -> Result<()> {
+    util::write_atomic(path, &document.data).context("캐시 저장 실패")?;
+    util::write_atomic(&signature_path(path), document.signature.as_bytes()).context("캐시 서명 저장 실패")
+}
+
+/// 최신 캐시를 덮어쓰고, 같은 문서를 설정 기록에도 남긴다.
+fn persist(config_dir: &Path, config: &TrackerConfig, document: &SignedDocument) -> Result<()> {
+    save_cache(&config_dir.join(CACHE_FILE), document)?;
+    ConfigHistory::open(config_dir).record(&config.last_updated, &document.data, &document.signature)
+}
+
+/// 캐시도 원격과 같은 서명 검증을 거친다. 서명이 없거나 맞지 않으면 사용하지 않는다.
+/// 기록이 생기기 전에 저장된 캐시(오프라인 모드 등)도 known-good이 될 수 있도록 설정 기록에 없으면 넣어 둔다.
+fn load_cache(config_dir: &Path) -> Result<TrackerConfig> {
+    let path = config_dir.join(CACHE_FILE);
+    let data = fs::read(&path).context("저장된 캐시가 없습니다.")?;
+    let signature = fs::read_to_string(signature_path(&path)).context("캐시 서명이 없습니다.")?;
+    let (config, document) = SignedDocument::verify(data, signature).context("캐시 검증 실패")?;
+
+    let mut history = ConfigHistory::open(config_dir);
+    if !history.contains(&config.last_updated)
+        && let Err(e) = history.record(&config.last_updated, &document.data, &document.signature)
+    {
+        log::warn!("캐시된 오프셋 설정을 기록하지 못했습니다: {}", e);
+    }
+    Ok(config)
+}
+
+/// file:// 소스. 원격과 마찬가지로 옆에 있는 .minisig 서명을 검증한다.
+fn read_signed_file(path: &Path) -> Result<(TrackerConfig, SignedDocument)> {
+    let data = fs::read(path).context("파일을 읽지 못했습니다.")?;
+    let signature = fs::read_to_string(signature_path(path)).context("서명 파일을 읽지 못했습니다.")?;
+    SignedDocument::verify(data, signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 오프셋 전용 키로 서명한 설정 문서
+    fn fixture_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/offsets_signed.json")
+    }
+
+    /// 픽스처를 캐시 파일로 둔 빈 설정 폴더
+    fn config_dir_with_cache(name: &str, data: &[u8]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("wuma-tracker-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let cache = dir.join(CACHE_FILE);
+        util::write_atomic(&cache, data).unwrap();
+        fs::copy(signature_path(&fixture_path()), signature_path(&cache)).unwrap();
+        dir
+    }
+
+    #[test]
+    fn cached_config_can_become_known_good() {
+        let dir = config_dir_with_cache("offsets-cache", &fs::read(fixture_path()).unwrap());
+        let config = load_cache(&dir).unwrap();
+        assert_eq!(config.last_updated, "2026-10-01T00:00:00Z");
+
+        // 원격에서 받은 적 없이 캐시만으로 동작한 설정도 known-good으로 표시되고 되돌리기에 쓸 수 있다.
+        let mut history = ConfigHistory::open(&dir);
+        assert!(history.contains(&config.last_updated));
+        history.mark_known_good(&config.last_updated).unwrap();
+        let history = ConfigHistory::open(&dir);
+        assert_eq!(history.known_good(), Some(config.last_updated.as_str()));
+        let (data, signature) = history.load(&config.last_updated).unwrap();
+        assert!(SignedDocument::verify(data, signature).is_ok());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn tampered_cache_is_not_recorded() {
+        let mut data = fs::read(fixture_path()).unwrap();
+        data.extend_from_slice(b" ");
+        let dir = config_dir_with_cache("offsets-cache-tampered", &data);
+        assert!(load_cache(&dir).is_err());
+        assert!(!ConfigHistory::open(&dir).contains("2026-10-01T00:00:00Z"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
@@ -1,12 +1,21 @@
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GWorldScanConfig {
     pub enabled: bool,
     /// MOV 명령어 앞 바이트열 (e.g. "48 8B 1D")
+    #[serde(default)]
     pub prefix: String,
     /// disp32 직후 바이트열, ?? = wildcard (e.g. "48 85 DB 74 ?? 41 B0 01")
+    #[serde(default)]
     pub suffix: String,
+    /// 우선순위 순서의 시그니처 목록. 비어 있으면 prefix/suffix를 "default" 시그니처로 사용한다.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signatures: Vec<GWorldSignature>,
+    /// offsets_override.json으로 덮어쓴 설정인지 여부
+    #[serde(skip)]
+    pub local_override: bool,
 }
 
 impl Default for GWorldScanConfig {
@@ -15,27 +24,504 @@ impl Default for GWorldScanConfig {
             enabled: true,
             prefix: "48 8B 1D".to_string(),
             suffix: "48 85 DB 74 ?? 41 B0 01".to_string(),
+            signatures: Vec::new(),
+            local_override: false,
         }
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+impl GWorldScanConfig {
+    pub fn signatures(&self) -> Cow<'_, [GWorldSignature]> {
+        if !self.signatures.is_empty() {
+            return Cow::Borrowed(&self.signatures);
+        }
+        if self.prefix.is_empty() {
+            return Cow::Owned(Vec::new());
+        }
+        Cow::Owned(vec![GWorldSignature::from_prefix_suffix(&self.prefix, &self.suffix)])
+    }
+}
+
+/// GWorld를 참조하는 코드 패턴 (x86_64: RIP-relative, arm64: ADRP + LDR/ADD)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GWorldSignature {
+    pub name: String,
+    /// 명령어 시작부터의 바이트열, ?? = wildcard (disp32 자리도 ??)
+    /// e.g. "48 8B 1D ?? ?? ?? ?? 48 85 DB 74 ?? 41 B0 01"
+    pub pattern: String,
+    /// x86_64: 패턴 시작 → disp32 위치
+    /// arm64: 패턴 시작 → ADRP 위치 (바로 다음 명령어가 같은 레지스터의 LDR/ADD)
+    pub disp_offset: usize,
+    /// 패턴 시작 → 다음 명령어 (RIP 기준점). arm64에서는 쓰지 않는다.
+    #[serde(default)]
+    pub instr_len: usize,
+    #[serde(default)]
+    pub arch: SignatureArch,
+}
+
+/// 시그니처가 대상으로 하는 명령어 집합. PE 스캔은 x86_64만 사용한다.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignatureArch {
+    #[default]
+    #[serde(rename = "x86_64")]
+    X86_64,
+    #[serde(rename = "arm64")]
+    Arm64,
+}
+
+impl GWorldSignature {
+    /// 레거시 prefix/suffix 쌍: prefix + disp32 + suffix, 명령어 길이 = prefix + 4
+    fn from_prefix_suffix(prefix: &str, suffix: &str) -> Self {
+        let prefix_len = prefix.split_whitespace().count();
+        Self {
+            name: "default".to_string(),
+            pattern: format!("{} ?? ?? ?? ?? {}", prefix, suffix),
+            disp_offset: prefix_len,
+            instr_len: prefix_len + 4,
+            arch: SignatureArch::X86_64,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TrackerConfig {
     pub last_updated: String,
     pub gworld_scan: GWorldScanConfig,
     pub offsets: Vec<WuwaOffset>,
+    /// 맵 이름 해석 설정. 없으면 맵 이름을 읽지 않는다.
+    #[serde(default)]
+    pub names: Option<NameConfig>,
+}
+
+impl TrackerConfig {
+    /// 로컬 override를 원격 설정 위에 덮어쓴다. override variant는 원격 variant보다 먼저 시도된다.
+    pub fn with_override(mut self, local: &TrackerConfigOverride) -> Self {
+        let scan = &local.gworld_scan;
+        if *scan != GWorldScanOverride::default() {
+            // prefix/suffix만 덮어쓰면 원격 signatures가 우선하지 않도록 비운다.
+            if scan.prefix.is_some() || scan.suffix.is_some() {
+                self.gworld_scan.signatures.clear();
+            }
+            if let Some(enabled) = scan.enabled {
+                self.gworld_scan.enabled = enabled;
+            }
+            if let Some(prefix) = &scan.prefix {
+                self.gworld_scan.prefix = prefix.clone();
+            }
+            if let Some(suffix) = &scan.suffix {
+                self.gworld_scan.suffix = suffix.clone();
+            }
+            if let Some(signatures) = &scan.signatures {
+                self.gworld_scan.signatures = signatures.clone();
+            }
+            self.gworld_scan.local_override = true;
+        }
+
+        let mut offsets: Vec<WuwaOffset> = local
+            .offsets
+            .iter()
+            .cloned()
+            .map(|mut offset| {
+                offset.local_override = true;
+                offset
+            })
+            .collect();
+        offsets.append(&mut self.offsets);
+        self.offsets = offsets;
+        self
+    }
+}
+
+/// 앱 설정 폴더의 offsets_override.json. 공식 오프셋이 갱신되기 전에 직접 찾은 값을 넣어 쓴다.
+///
+/// ```json
+/// {
+///   "offsets": [ { "name": "my-fix", "global_gworld": 123456, ... } ],
+///   "gworld_scan": { "signatures": [ ... ] }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct TrackerConfigOverride {
+    /// 원격 variant보다 먼저 시도할 variant 목록
+    #[serde(default)]
+    pub offsets: Vec<WuwaOffset>,
+    #[serde(default)]
+    pub gworld_scan: GWorldScanOverride,
+}
+
+/// 지정한 필드만 원격 GWorldScanConfig 위에 덮어쓴다.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct GWorldScanOverride {
+    pub enabled: Option<bool>,
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    pub signatures: Option<Vec<GWorldSignature>>,
+}
+
+/// FNamePool(GNames) 기반 이름 해석 설정 (UE 4.23+ 레이아웃)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NameConfig {
+    /// FNamePool RVA
+    pub gnames: u64,
+    /// FNamePool 안의 Blocks[] 오프셋
+    #[serde(default = "default_pool_blocks")]
+    pub pool_blocks: u64,
+    /// FNameEntry 정렬 단위
+    #[serde(default = "default_entry_stride")]
+    pub entry_stride: u64,
+    /// FNameEntryHeader에서 Len 비트의 시작 위치
+    #[serde(default = "default_header_len_shift")]
+    pub header_len_shift: u32,
+    /// UObjectBase::NamePrivate
+    #[serde(default = "default_uobject_name")]
+    pub uobject_name: u64,
+    /// UObjectBase::OuterPrivate
+    #[serde(default = "default_uobject_outer")]
+    pub uobject_outer: u64,
+    /// UWorld → 맵 이름 FName 체인 (마지막은 "read": "name").
+    /// 비어 있으면 PersistentLevel의 Outer(UWorld) 이름을 읽는다.
+    #[serde(default)]
+    pub map_name: Vec<ChainStep>,
+}
+
+fn default_pool_blocks() -> u64 { 0x10 }
+fn default_entry_stride() -> u64 { 2 }
+fn default_header_len_shift() -> u32 { 6 }
+fn default_uobject_name() -> u64 { 0x18 }
+fn default_uobject_outer() -> u64 { 0x20 }
+
+impl NameConfig {
+    pub fn map_name_chain(&self, offset: &WuwaOffset) -> Cow<'_, [ChainStep]> {
+        if !self.map_name.is_empty() {
+            return Cow::Borrowed(&self.map_name);
+        }
+        Cow::Owned(vec![
+            ChainStep::Add { value: offset.uworld_persistentlevel },
+            ChainStep::Deref { name: "PersistentLevel".to_string() },
+            ChainStep::Add { value: self.uobject_outer },
+            ChainStep::Deref { name: "Outer".to_string() },
+            ChainStep::Add { value: self.uobject_name },
+            ChainStep::Read { value: ChainValue::Name },
+        ])
+    }
 }
 
+/// 오프셋 variant 하나.
+/// `chain`이 있으면 그대로 해석하고, 없으면 아래 고정 필드로 기존과 같은 체인을 만든다.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct WuwaOffset {
     pub name: String,
     pub global_gworld: u64,
+    #[serde(default)]
     pub uworld_persistentlevel: u64,
+    #[serde(default)]
     pub uworld_owninggameinstance: u64,
+    #[serde(default)]
     pub ulevel_lastworldorigin: u64,
+    #[serde(default)]
     pub ugameinstance_localplayers: u64,
+    #[serde(default)]
     pub uplayer_playercontroller: u64,
+    #[serde(default)]
     pub aplayercontroller_acknowlegedpawn: u64,
+    #[serde(default)]
     pub aactor_rootcomponent: u64,
+    #[serde(default)]
     pub uscenecomponent_componenttoworld: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain: Option<PlayerChain>,
+    /// 플레이어 FTransform의 메모리 배치. 없으면 TransformLayout::default() (f32)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform_layout: Option<TransformLayout>,
+    /// 이 variant가 적용되는 게임 빌드. build_id("pe:...", "macho:...") 또는 버전("2.4") 목록.
+    /// 비어 있으면 모든 버전에 적용된다.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub game_versions: Vec<String>,
+    /// offsets_override.json에서 온 variant인지 여부
+    #[serde(skip)]
+    pub local_override: bool,
+}
+
+impl WuwaOffset {
+    pub fn transform_layout(&self) -> TransformLayout {
+        self.transform_layout.unwrap_or_default()
+    }
+
+    pub fn player_chain(&self) -> Cow<'_, PlayerChain> {
+        match &self.chain {
+            Some(chain) => Cow::Borrowed(chain),
+            None => Cow::Owned(self.legacy_chain()),
+        }
+    }
+
+    fn legacy_chain(&self) -> PlayerChain {
+        use ChainStep::*;
+        let deref = |name: &str| Deref { name: name.to_string() };
+        PlayerChain {
+            transform: vec![
+                Add { value: self.uworld_owninggameinstance },
+                deref("OwningGameInstance"),
+                Add { value: self.ugameinstance_localplayers },
+                deref("TArray<*LocalPlayers>"),
+                Index { index: 0, stride: 8 },
+                deref("LocalPlayer"),
+                Add { value: self.uplayer_playercontroller },
+                deref("PlayerController"),
+                Add { value: self.aplayercontroller_acknowlegedpawn },
+                deref("APawn"),
+                Add { value: self.aactor_rootcomponent },
+                deref("RootComponent"),
+                Add { value: self.uscenecomponent_componenttoworld },
+                Read { value: ChainValue::Transform },
+            ],
+            world_origin: vec![
+                Add { value: self.uworld_persistentlevel },
+                deref("PersistentLevel"),
+                Add { value: self.ulevel_lastworldorigin },
+                Read { value: ChainValue::IntVector },
+            ],
+        }
+    }
+}
+
+/// UWorld 주소에서 시작하는 포인터 체인 묶음.
+///
+/// ```json
+/// "chain": {
+///   "transform": [
+///     { "op": "add", "value": 440 }, { "op": "deref", "name": "OwningGameInstance" },
+///     { "op": "add", "value": 56 }, { "op": "deref", "name": "LocalPlayers" },
+///     { "op": "index", "index": 0, "stride": 8 }, { "op": "deref", "name": "LocalPlayer" },
+///     ...
+///     { "op": "read", "type": "transform" }
+///   ],
+///   "world_origin": [ ..., { "op": "read", "type": "int_vector" } ]
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlayerChain {
+    /// UWorld → 플레이어 FTransform
+    pub transform: Vec<ChainStep>,
+    /// UWorld → 월드 원점 FIntVector
+    pub world_origin: Vec<ChainStep>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ChainStep {
+    /// 현재 주소에 필드 오프셋을 더한다.
+    Add { value: u64 },
+    /// 현재 주소의 포인터 값을 읽어 따라간다. name은 진단 메시지용.
+    Deref {
+        #[serde(default)]
+        name: String,
+    },
+    /// 배열의 index번째 원소로 이동한다. (index * stride 만큼 더함)
+    Index { index: u64, stride: u64 },
+    /// 현재 주소의 최종 구조체를 읽는다. 체인의 마지막 단계여야 한다.
+    Read {
+        #[serde(rename = "type")]
+        value: ChainValue,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainValue {
+    Transform,
+    IntVector,
+    /// FName (ComparisonIndex + Number)
+    Name,
+}
+
+/// FTransform을 이루는 필드의 저장 형식.
+/// 기본값은 UE4 배치(f32, Rotation → Translation → Scale3D, 필드마다 16바이트)이고,
+/// UE5 large world coordinates 빌드는 f64 + 32바이트 간격이다.
+///
+/// ```json
+/// "transform_layout": { "scalar": "f64", "order": ["rotation", "translation", "scale"], "stride": 32 }
+/// ```
+///
+/// 설정을 파싱할 때 검증하므로(잘못된 배치가 있는 설정은 통째로 거부) 만들어진 값은 항상 유효하다.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "TransformLayoutSpec", into = "TransformLayoutSpec")]
+pub struct TransformLayout {
+    spec: TransformLayoutSpec,
+    /// TransformField 순서대로, 각 필드가 order에서 몇 번째인지
+    slots: [usize; 3],
+}
+
+/// 설정 JSON 형식 그대로의 transform_layout
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+struct TransformLayoutSpec {
+    scalar: TransformScalar,
+    #[serde(default = "default_transform_order")]
+    order: [TransformField; 3],
+    /// 필드 시작 사이의 간격(바이트). 없으면 4 × scalar 크기 (정렬된 벡터 레지스터 배치)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stride: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransformScalar {
+    F32,
+    F64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransformField {
+    /// 쿼터니언 (x, y, z, w)
+    Rotation,
+    Translation,
+    Scale,
+}
+
+const TRANSFORM_FIELDS: [TransformField; 3] =
+    [TransformField::Rotation, TransformField::Translation, TransformField::Scale];
+
+fn default_transform_order() -> [TransformField; 3] {
+    TRANSFORM_FIELDS
+}
+
+impl Default for TransformLayout {
+    fn default() -> Self {
+        let spec = TransformLayoutSpec { scalar: TransformScalar::F32, order: default_transform_order(), stride: None };
+        Self { spec, slots: [0, 1, 2] }
+    }
+}
+
+impl TryFrom<TransformLayoutSpec> for TransformLayout {
+    type Error = String;
+
+    /// 필드가 한 번씩만 나오고, 간격이 가장 긴 필드(rotation)보다 짧지 않은지 확인한다.
+    fn try_from(spec: TransformLayoutSpec) -> Result<Self, String> {
+        let mut slots = [0; 3];
+        for field in TRANSFORM_FIELDS {
+            let mut found = spec.order.iter().enumerate().filter(|(_, f)| **f == field).map(|(i, _)| i);
+            match (found.next(), found.next()) {
+                (Some(slot), None) => slots[field as usize] = slot,
+                _ => return Err(format!("transform_layout.order에 {:?}가 정확히 한 번 있어야 합니다.", field)),
+            }
+        }
+        let layout = Self { spec, slots };
+        let min_stride = TransformField::Rotation.components() * layout.scalar_size();
+        if layout.stride() < min_stride || layout.stride() > 256 {
+            return Err(format!(
+                "transform_layout.stride {}가 범위({}..=256)를 벗어납니다.",
+                layout.stride(),
+                min_stride
+            ));
+        }
+        Ok(layout)
+    }
+}
+
+impl From<TransformLayout> for TransformLayoutSpec {
+    fn from(layout: TransformLayout) -> Self {
+        layout.spec
+    }
+}
+
+impl TransformField {
+    pub fn components(self) -> usize {
+        match self {
+            TransformField::Rotation => 4,
+            TransformField::Translation | TransformField::Scale => 3,
+        }
+    }
+}
+
+impl TransformLayout {
+    pub fn scalar(&self) -> TransformScalar {
+        self.spec.scalar
+    }
+
+    pub fn scalar_size(&self) -> usize {
+        match self.spec.scalar {
+            TransformScalar::F32 => 4,
+            TransformScalar::F64 => 8,
+        }
+    }
+
+    pub fn stride(&self) -> usize {
+        self.spec.stride.map_or(4 * self.scalar_size(), |stride| stride as usize)
+    }
+
+    /// field가 시작하는 바이트 위치
+    pub fn field_offset(&self, field: TransformField) -> usize {
+        self.slots[field as usize] * self.stride()
+    }
+
+    /// 한 번에 읽어야 하는 바이트 수 (마지막 필드의 끝)
+    pub fn byte_len(&self) -> usize {
+        TRANSFORM_FIELDS
+            .iter()
+            .map(|f| self.field_offset(*f) + f.components() * self.scalar_size())
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_offset(layout: &str) -> serde_json::Result<WuwaOffset> {
+        serde_json::from_str(&format!(r#"{{"name":"v","global_gworld":0,"transform_layout":{}}}"#, layout))
+    }
+
+    #[test]
+    fn defaults_to_f32_layout() {
+        let offset: WuwaOffset = serde_json::from_str(r#"{"name":"v","global_gworld":0}"#).unwrap();
+        let layout = offset.transform_layout();
+        assert_eq!(layout.field_offset(TransformField::Translation), 16);
+        assert_eq!(layout.byte_len(), 44);
+    }
+
+    #[test]
+    fn parses_reordered_f64_layout() {
+        let json = r#"{"scalar":"f64","order":["translation","rotation","scale"]}"#;
+        let layout = parse_offset(json).unwrap().transform_layout();
+        assert_eq!(layout.field_offset(TransformField::Translation), 0);
+        assert_eq!(layout.field_offset(TransformField::Rotation), 32);
+        assert_eq!(layout.field_offset(TransformField::Scale), 64);
+        assert_eq!(layout.byte_len(), 88);
+        assert_eq!(serde_json::to_string(&layout).unwrap(), json);
+    }
+
+    #[test]
+    fn rejects_invalid_layout_when_parsing() {
+        assert!(parse_offset(r#"{"scalar":"f32","order":["rotation","rotation","scale"]}"#).is_err());
+        assert!(parse_offset(r#"{"scalar":"f32","stride":0}"#).is_err());
+        // f64 쿼터니언(32바이트)보다 좁은 간격
+        assert!(parse_offset(r#"{"scalar":"f64","stride":16}"#).is_err());
+    }
+
+    #[test]
+    fn legacy_prefix_suffix_becomes_default_signature() {
+        let config: GWorldScanConfig =
+            serde_json::from_str(r#"{"enabled":true,"prefix":"48 8B 05","suffix":"48 85 C0"}"#).unwrap();
+        let signatures = config.signatures();
+        assert_eq!(signatures.len(), 1);
+        assert_eq!(signatures[0].name, "default");
+        assert_eq!(signatures[0].pattern, "48 8B 05 ?? ?? ?? ?? 48 85 C0");
+        assert_eq!((signatures[0].disp_offset, signatures[0].instr_len), (3, 7));
+        assert_eq!(signatures[0].arch, SignatureArch::X86_64);
+
+        let none: GWorldScanConfig = serde_json::from_str(r#"{"enabled":true}"#).unwrap();
+        assert!(none.signatures().is_empty());
+    }
+
+    #[test]
+    fn explicit_signatures_replace_prefix_suffix() {
+        let config: GWorldScanConfig = serde_json::from_str(
+            r#"{"enabled":true,"prefix":"48 8B 1D","suffix":"48 85 DB",
+                "signatures":[{"name":"rax","pattern":"48 8B 05 ?? ?? ?? ??","disp_offset":3,"instr_len":7}]}"#,
+        )
+        .unwrap();
+        let names: Vec<_> = config.signatures().iter().map(|s| s.name.clone()).collect();
+        assert_eq!(names, ["rax"]);
+    }
 }
@@ -0,0 +1,314 @@
+use std::ops::Range;
+
+use anyhow::{Context, Result, bail, ensure};
+
+use crate::process_backend::ProcessBackend;
+
+const IMAGE_DOS_SIGNATURE: u16 = 0x5A4D; // "MZ"
+const IMAGE_NT_SIGNATURE: u32 = 0x0000_4550; // "PE\0\0"
+const IMAGE_FILE_MACHINE_AMD64: u16 = 0x8664;
+const IMAGE_NT_OPTIONAL_HDR64_MAGIC: u16 = 0x20B;
+const IMAGE_DIRECTORY_ENTRY_EXCEPTION: usize = 3;
+const IMAGE_SCN_MEM_EXECUTE: u32 = 0x2000_0000;
+const IMAGE_SCN_MEM_WRITE: u32 = 0x8000_0000;
+
+const DOS_HEADER_SIZE: usize = 64;
+/// "PE\0\0" + IMAGE_FILE_HEADER
+const NT_HEADER_SIZE: usize = 24;
+/// IMAGE_OPTIONAL_HEADER64에서 DataDirectory 배열이 시작하는 위치
+const OPTIONAL_HEADER64_DIRECTORIES: usize = 112;
+const SECTION_HEADER_SIZE: usize = 40;
+/// 잘못된 헤더 값으로 엉뚱한 주소를 읽거나 큰 버퍼를 잡지 않도록 하는 상한
+const MAX_LFANEW: u32 = 0x1_0000;
+const MAX_SECTIONS: u16 = 96;
+const MAX_DIRECTORIES: usize = 16;
+
+/// 검증된 PE32+ 헤더 정보. 로드된 모듈(ProcessBackend)이나 파일 바이트에서 읽는다.
+#[derive(Clone, Debug)]
+pub struct PeImage {
+    pub timestamp: u32,
+    pub size_of_image: u32,
+    pub size_of_headers: u32,
+    pub sections: Vec<PeSection>,
+    pub data_directories: Vec<DataDirectory>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DataDirectory {
+    pub rva: u32,
+    pub size: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct PeSection {
+    pub name: String,
+    pub virtual_address: u32,
+    pub virtual_size: u32,
+    pub pointer_to_raw_data: u32,
+    pub size_of_raw_data: u32,
+    pub characteristics: u32,
+}
+
+impl PeSection {
+    /// 메모리상 RVA 범위. VirtualSize가 0인 오래된 링커 출력은 SizeOfRawData를 쓴다.
+    pub fn rva_range(&self) -> Range<u64> {
+        let size = if self.virtual_size != 0 { self.virtual_size } else { self.size_of_raw_data };
+        self.virtual_address as u64..self.virtual_address as u64 + size as u64
+    }
+
+    pub fn is_executable(&self) -> bool {
+        self.characteristics & IMAGE_SCN_MEM_EXECUTE != 0
+    }
+
+    pub fn is_writable(&self) -> bool {
+        self.characteristics & IMAGE_SCN_MEM_WRITE != 0
+    }
+}
+
+impl PeImage {
+    /// base에 로드된 모듈의 헤더를 읽는다.
+    pub fn read<B: ProcessBackend>(backend: &B, base: u64) -> Result<Self> {
+        Self::parse_with(|offset, buf| Ok(backend.read_bytes(base + offset, buf)?))
+    }
+
+    /// 파일 내용에서 헤더를 읽는다. (헤더 영역은 파일 오프셋과 RVA가 같다)
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        Self::parse_with(|offset, buf| {
+            let start = offset as usize;
+            let src = bytes
+                .get(start..start + buf.len())
+                .with_context(|| format!("파일 크기를 벗어난 헤더 (0x{:X}+0x{:X})", offset, buf.len()))?;
+            buf.copy_from_slice(src);
+            Ok(())
+        })
+    }
+
+    fn parse_with(mut read: impl FnMut(u64, &mut [u8]) -> Result<()>) -> Result<Self> {
+        let mut dos = [0u8; DOS_HEADER_SIZE];
+        read(0, &mut dos).context("DOS 헤더 읽기 실패")?;
+        ensure!(u16_at(&dos, 0) == IMAGE_DOS_SIGNATURE, "MZ 시그니처가 없습니다.");
+        let e_lfanew = u32_at(&dos, 0x3C);
+        ensure!(
+            (DOS_HEADER_SIZE as u32..MAX_LFANEW).contains(&e_lfanew),
+            "비정상 e_lfanew: 0x{:X}",
+            e_lfanew
+        );
+
+        let nt = e_lfanew as u64;
+        let mut file_header = [0u8; NT_HEADER_SIZE];
+        read(nt, &mut file_header).context("NT 헤더 읽기 실패")?;
+        ensure!(u32_at(&file_header, 0) == IMAGE_NT_SIGNATURE, "PE 시그니처가 없습니다.");
+        let machine = u16_at(&file_header, 4);
+        ensure!(machine == IMAGE_FILE_MACHINE_AMD64, "x64 이미지가 아닙니다. (Machine 0x{:X})", machine);
+        let number_of_sections = u16_at(&file_header, 6);
+        ensure!(number_of_sections <= MAX_SECTIONS, "섹션 수가 비정상입니다: {}", number_of_sections);
+        let timestamp = u32_at(&file_header, 8);
+        let size_of_optional_header = u16_at(&file_header, 20) as usize;
+        ensure!(
+            size_of_optional_header >= OPTIONAL_HEADER64_DIRECTORIES,
+            "Optional Header가 너무 작습니다: 0x{:X}",
+            size_of_optional_header
+        );
+
+        let mut optional = vec![0u8; size_of_optional_header];
+        read(nt + NT_HEADER_SIZE as u64, &mut optional).context("Optional Header 읽기 실패")?;
+        let magic = u16_at(&optional, 0);
+        ensure!(
+            magic == IMAGE_NT_OPTIONAL_HDR64_MAGIC,
+            "PE32+ Optional Header가 아닙니다. (magic 0x{:X})",
+            magic
+        );
+        let size_of_image = u32_at(&optional, 56);
+        let size_of_headers = u32_at(&optional, 60);
+        let number_of_rva_and_sizes = u32_at(&optional, 108) as usize;
+        let directory_count = number_of_rva_and_sizes
+            .min((size_of_optional_header - OPTIONAL_HEADER64_DIRECTORIES) / 8)
+            .min(MAX_DIRECTORIES);
+        let data_directories = (0..directory_count)
+            .map(|i| {
+                let at = OPTIONAL_HEADER64_DIRECTORIES + i * 8;
+                DataDirectory { rva: u32_at(&optional, at), size: u32_at(&optional, at + 4) }
+            })
+            .collect();
+
+        let mut table = vec![0u8; number_of_sections as usize * SECTION_HEADER_SIZE];
+        read(nt + (NT_HEADER_SIZE + size_of_optional_header) as u64, &mut table).context("섹션 테이블 읽기 실패")?;
+        let sections: Vec<PeSection> = table
+            .chunks_exact(SECTION_HEADER_SIZE)
+            .map(|s| PeSection {
+                name: String::from_utf8_lossy(&s[..8]).trim_end_matches('\0').to_string(),
+                virtual_size: u32_at(s, 8),
+                virtual_address: u32_at(s, 12),
+                size_of_raw_data: u32_at(s, 16),
+                pointer_to_raw_data: u32_at(s, 20),
+                characteristics: u32_at(s, 36),
+            })
+            .collect();
+        if let Some(s) = sections.iter().find(|s| s.rva_range().end > size_of_image as u64) {
+            bail!("섹션 '{}'이 SizeOfImage(0x{:X})를 벗어납니다.", s.name, size_of_image);
+        }
+
+        Ok(Self { timestamp, size_of_image, size_of_headers, sections, data_directories })
+    }
+
+    pub fn section(&self, name: &str) -> Option<&PeSection> {
+        self.sections.iter().find(|s| s.name == name)
+    }
+
+    pub fn section_containing(&self, rva: u64) -> Option<&PeSection> {
+        self.sections.iter().find(|s| s.rva_range().contains(&rva))
+    }
+
+    pub fn text(&self) -> Option<Range<u64>> {
+        self.section(".text").map(PeSection::rva_range)
+    }
+
+    pub fn data(&self) -> Option<Range<u64>> {
+        self.section(".data").map(PeSection::rva_range)
+    }
+
+    /// 예외 디렉터리(.pdata, RUNTIME_FUNCTION 배열). 디렉터리가 비어 있으면 섹션 이름으로 찾는다.
+    pub fn pdata(&self) -> Option<Range<u64>> {
+        match self.data_directories.get(IMAGE_DIRECTORY_ENTRY_EXCEPTION) {
+            Some(dir) if dir.rva != 0 && dir.size != 0 => Some(dir.rva as u64..dir.rva as u64 + dir.size as u64),
+            _ => self.section(".pdata").map(PeSection::rva_range),
+        }
+    }
+
+    /// [start, end)가 통째로 실행 가능한 섹션 안에 있는지
+    pub fn is_executable(&self, range: Range<u64>) -> bool {
+        self.section_containing(range.start)
+            .is_some_and(|s| s.is_executable() && range.end <= s.rva_range().end)
+    }
+
+    /// 전역 변수(GWorld 등)가 있을 수 있는 쓰기 가능한 데이터 섹션인지
+    pub fn is_writable_data(&self, rva: u64) -> bool {
+        self.section_containing(rva)
+            .is_some_and(|s| s.is_writable() && !s.is_executable())
+    }
+}
+
+fn u16_at(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes(bytes[at..at + 2].try_into().unwrap())
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    const IMAGE_SCN_MEM_READ: u32 = 0x4000_0000;
+    pub(crate) const CODE: u32 = IMAGE_SCN_MEM_EXECUTE | IMAGE_SCN_MEM_READ;
+    pub(crate) const RDATA: u32 = IMAGE_SCN_MEM_READ;
+    pub(crate) const DATA: u32 = IMAGE_SCN_MEM_WRITE | IMAGE_SCN_MEM_READ;
+
+    const E_LFANEW: usize = 0x40;
+    const OPTIONAL: usize = E_LFANEW + NT_HEADER_SIZE;
+    const SIZE_OF_OPTIONAL_HEADER: usize = OPTIONAL_HEADER64_DIRECTORIES + MAX_DIRECTORIES * 8;
+    const SIZE_OF_HEADERS: usize = 0x400;
+
+    /// 최소한의 PE32+ 헤더 (SizeOfHeaders 바이트). 섹션은 (이름, RVA 범위, Characteristics)이고
+    /// 파일 오프셋은 RVA와 같게 둔다. SizeOfImage는 마지막 섹션 끝을 0x1000 단위로 올린 값이다.
+    pub(crate) fn pe_headers(timestamp: u32, sections: &[(&str, Range<u32>, u32)]) -> Vec<u8> {
+        let mut bytes = vec![0u8; SIZE_OF_HEADERS];
+        let mut put = |at: usize, value: &[u8]| bytes[at..at + value.len()].copy_from_slice(value);
+        let size_of_image = sections.iter().map(|(_, range, _)| range.end).max().unwrap_or(0).next_multiple_of(0x1000);
+
+        put(0, &IMAGE_DOS_SIGNATURE.to_le_bytes());
+        put(0x3C, &(E_LFANEW as u32).to_le_bytes());
+        put(E_LFANEW, &IMAGE_NT_SIGNATURE.to_le_bytes());
+        put(E_LFANEW + 4, &IMAGE_FILE_MACHINE_AMD64.to_le_bytes());
+        put(E_LFANEW + 6, &(sections.len() as u16).to_le_bytes());
+        put(E_LFANEW + 8, &timestamp.to_le_bytes());
+        put(E_LFANEW + 20, &(SIZE_OF_OPTIONAL_HEADER as u16).to_le_bytes());
+        put(OPTIONAL, &IMAGE_NT_OPTIONAL_HDR64_MAGIC.to_le_bytes());
+        put(OPTIONAL + 56, &size_of_image.to_le_bytes());
+        put(OPTIONAL + 60, &(SIZE_OF_HEADERS as u32).to_le_bytes());
+        put(OPTIONAL + 108, &(MAX_DIRECTORIES as u32).to_le_bytes());
+
+        let table = OPTIONAL + SIZE_OF_OPTIONAL_HEADER;
+        for (i, (name, range, characteristics)) in sections.iter().enumerate() {
+            let at = table + i * SECTION_HEADER_SIZE;
+            let size = range.end - range.start;
+            put(at, name.as_bytes());
+            put(at + 8, &size.to_le_bytes());
+            put(at + 12, &range.start.to_le_bytes());
+            put(at + 16, &size.to_le_bytes());
+            put(at + 20, &range.start.to_le_bytes());
+            put(at + 36, &characteristics.to_le_bytes());
+        }
+        bytes
+    }
+
+    fn minimal() -> Vec<u8> {
+        pe_headers(
+            0x6650_0000,
+            &[(".text", 0x1000..0x1800, CODE), (".rdata", 0x2000..0x2400, RDATA), (".data", 0x3000..0x3100, DATA)],
+        )
+    }
+
+    fn parse_error(bytes: &[u8]) -> String {
+        format!("{:#}", PeImage::parse(bytes).unwrap_err())
+    }
+
+    #[test]
+    fn parses_minimal_pe32_plus() {
+        let image = PeImage::parse(&minimal()).unwrap();
+        assert_eq!(image.timestamp, 0x6650_0000);
+        assert_eq!(image.size_of_image, 0x4000);
+        assert_eq!(image.size_of_headers, SIZE_OF_HEADERS as u32);
+        assert_eq!(image.sections.len(), 3);
+        assert_eq!(image.text(), Some(0x1000..0x1800));
+        assert_eq!(image.data(), Some(0x3000..0x3100));
+        assert_eq!(image.pdata(), None);
+
+        assert!(image.is_executable(0x1000..0x1800));
+        assert!(!image.is_executable(0x17F0..0x1810));
+        assert!(image.is_writable_data(0x3008));
+        assert!(!image.is_writable_data(0x2008));
+        assert!(!image.is_writable_data(0x1008));
+    }
+
+    #[test]
+    fn rejects_bad_signatures() {
+        let mut bytes = minimal();
+        bytes[0] = b'X';
+        assert!(parse_error(&bytes).contains("MZ"));
+
+        let mut bytes = minimal();
+        bytes[E_LFANEW + 2] = 1;
+        assert!(parse_error(&bytes).contains("PE 시그니처"));
+
+        let mut bytes = minimal();
+        bytes[0x3C..0x40].copy_from_slice(&0x20u32.to_le_bytes());
+        assert!(parse_error(&bytes).contains("e_lfanew"));
+    }
+
+    #[test]
+    fn rejects_non_amd64_and_pe32() {
+        let mut bytes = minimal();
+        bytes[E_LFANEW + 4..E_LFANEW + 6].copy_from_slice(&0x014Cu16.to_le_bytes());
+        assert!(parse_error(&bytes).contains("Machine 0x14C"));
+
+        let mut bytes = minimal();
+        bytes[OPTIONAL..OPTIONAL + 2].copy_from_slice(&0x10Bu16.to_le_bytes());
+        assert!(parse_error(&bytes).contains("magic 0x10B"));
+    }
+
+    #[test]
+    fn rejects_sections_outside_the_image() {
+        let mut bytes = minimal();
+        bytes[OPTIONAL + 56..OPTIONAL + 60].copy_from_slice(&0x3000u32.to_le_bytes());
+        assert!(parse_error(&bytes).contains(".data"));
+    }
+
+    #[test]
+    fn rejects_truncated_headers() {
+        let bytes = minimal();
+        assert!(parse_error(&bytes[..0x160]).contains("섹션 테이블"));
+        assert!(parse_error(&bytes[..0x100]).contains("Optional Header"));
+    }
+}
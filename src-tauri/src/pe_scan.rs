@@ -0,0 +1,689 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::analysis_cache::{self, AnalysisCache, BuildKey, ResolvedSymbol};
+use crate::game_version::{GameBuild, pe_file_version};
+use crate::offsets::{GWorldScanConfig, GWorldSignature, SignatureArch, WuwaOffset};
+use crate::pe_image::PeImage;
+use crate::process_backend::{ProcessBackend, looks_like_uobject, probe_player_chain};
+use crate::sig_scan::Pattern;
+use crate::types::{NativeError, ReadFailure, ReadFault};
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+// .pdata 스캔 배치 파라미터
+const BATCH_GAP: u64 = 4096;
+const MAX_BATCH_SIZE: usize = 256 * 1024;
+
+// ── 시그니처 ──────────────────────────────────────────────────────────────────
+
+struct CompiledSignature<'a> {
+    name: &'a str,
+    pattern: Pattern,
+    disp_offset: usize,
+    instr_len: usize,
+}
+
+fn compile_signatures(signatures: &[GWorldSignature]) -> Vec<CompiledSignature<'_>> {
+    signatures
+        .iter()
+        .filter(|sig| sig.arch == SignatureArch::X86_64)
+        .filter_map(|sig| {
+            let pattern = match Pattern::parse(&sig.pattern) {
+                Ok(p) => p,
+                Err(e) => {
+                    log::warn!("GWorld 시그니처 '{}' 무시: {}", sig.name, e);
+                    return None;
+                }
+            };
+            // disp32가 패턴 안에 있어야 하고, 명령어 길이는 disp32 뒤여야 한다.
+            if sig.disp_offset + 4 > pattern.len() || sig.instr_len < sig.disp_offset + 4 {
+                log::warn!("GWorld 시그니처 '{}' 무시: 잘못된 disp_offset/instr_len", sig.name);
+                return None;
+            }
+            Some(CompiledSignature {
+                name: &sig.name,
+                pattern,
+                disp_offset: sig.disp_offset,
+                instr_len: sig.instr_len,
+            })
+        })
+        .collect()
+}
+
+impl CompiledSignature<'_> {
+    /// code[off..]에서 일치한 명령어의 RIP-relative 대상 RVA를 계산한다. (code_rva = code[0]의 RVA)
+    fn target_rva(&self, code: &[u8], off: usize, code_rva: u64) -> u64 {
+        let disp_at = off + self.disp_offset;
+        let disp = i32::from_le_bytes(code[disp_at..disp_at + 4].try_into().unwrap());
+        let instr_rva = code_rva + off as u64;
+        ((instr_rva as i64) + self.instr_len as i64 + disp as i64) as u64
+    }
+}
+
+// ── 후보 검증 ─────────────────────────────────────────────────────────────────
+
+/// 패턴 일치로 얻은 GWorld RVA 후보의 검증 결과
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum Candidate {
+    /// UObject가 아닌 값을 가리킴 → 오탐, 다음 일치로 넘어간다
+    Rejected,
+    /// 아직 월드 미진입(NULL) 등으로 체인을 확인할 수 없음 → 사용은 하되 캐시하지 않는다
+    Unverified,
+    /// UWorld이고 포인터 체인 전체가 성공함 → 캐시한다
+    Verified,
+}
+
+fn check_candidate<B: ProcessBackend>(
+    backend: &B,
+    base: u64,
+    size_of_image: u32,
+    rva: u64,
+    variants: &[WuwaOffset],
+) -> Candidate {
+    let Ok(gworld) = backend.read_memory::<u64>(base + rva) else {
+        return Candidate::Rejected;
+    };
+    if gworld == 0 {
+        return Candidate::Unverified;
+    }
+    if !looks_like_uobject(backend, gworld, base..base + size_of_image as u64) {
+        return Candidate::Rejected;
+    }
+    if probe_player_chain(backend, gworld, variants) {
+        Candidate::Verified
+    } else {
+        Candidate::Unverified
+    }
+}
+
+// ── 오프라인 스캔 결과 ────────────────────────────────────────────────────────
+
+/// scan_exe_file의 결과 (CLI/명령 출력 형식). seed_cache로 분석 캐시에 넣을 수 있다.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ExeScanResult {
+    pub exe_path: String,
+    pub pe_timestamp: u32,
+    pub size_of_image: u32,
+    pub gworld_rva: u64,
+    /// 일치한 시그니처 이름
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+/// 분석 캐시 키. build_id는 GameBuild::from_pe와 같은 형식이다.
+fn build_key(exe_path: &str, pe_timestamp: u32, size_of_image: u32) -> BuildKey {
+    game_build_key(exe_path, &GameBuild::from_pe(pe_timestamp, size_of_image, None))
+}
+
+/// 실행 중인 프로세스의 키 (WinProc/LinuxProc의 build_key)
+pub fn game_build_key(exe_path: &str, build: &GameBuild) -> BuildKey {
+    BuildKey { exe_path: cache_exe_name(exe_path), build_id: build.build_id.clone() }
+}
+
+/// 캐시 키에 쓰는 실행 파일 이름. 같은 파일이라도 GetModuleFileNameExW("C:\...\Client-Win64-Shipping.exe"),
+/// Wine의 /proc/pid/maps("/home/.../client-win64-shipping.exe"), 오프라인 스캔 인자의 경로가 모두 다르므로
+/// 소문자 파일 이름만 남긴다. 빌드는 build_id(TimeDateStamp + SizeOfImage)로 구분한다.
+fn cache_exe_name(exe_path: &str) -> String {
+    exe_path
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn gworld_symbol(rva: u64, signature: Option<String>) -> ResolvedSymbol {
+    ResolvedSymbol { name: analysis_cache::GWORLD.to_string(), address: rva, source: signature }
+}
+
+// ── 진입점 ────────────────────────────────────────────────────────────────────
+
+/// 프로세스 연결 직후 GWorld RVA를 결정한다. (캐시 → .pdata 스캔)
+/// 실패하거나 원격 설정으로 비활성화된 경우 0을 반환하며, 이때는 오프셋의 global_gworld로 폴백한다.
+/// 스캔 후보는 variants의 포인터 체인으로 검증하며, 검증된 결과만 캐시한다.
+pub fn initial_gworld_rva<B: ProcessBackend>(
+    backend: &B,
+    base: u64,
+    exe_path: &str,
+    cache_dir: &Path,
+    scan_config: &GWorldScanConfig,
+    variants: &[WuwaOffset],
+) -> u64 {
+    if !scan_config.enabled {
+        log::info!("GWorld 자동 탐색 비활성화됨 (원격 설정), 오프셋 폴백 사용");
+        return 0;
+    }
+    let signatures = scan_config.signatures();
+    match find_gworld_rva_with_cache(backend, base, exe_path, cache_dir, &signatures, variants) {
+        Ok(rva) => rva,
+        Err(e) => {
+            log::warn!("GWorld 스캔 실패, 오프셋 폴백 사용: {}", e);
+            0
+        }
+    }
+}
+
+/// 캐시를 무시하고 다시 스캔한다. 성공하면 캐시를 갱신한다.
+pub fn rescan_gworld_rva<B: ProcessBackend>(
+    backend: &B,
+    base: u64,
+    exe_path: &str,
+    cache_dir: &Path,
+    scan_config: &GWorldScanConfig,
+    variants: &[WuwaOffset],
+) -> Result<u64> {
+    let signatures = scan_config.signatures();
+    let (rva, signature, candidate) = scan_gworld_rva(backend, base, &signatures, variants)?;
+    let mut cache = AnalysisCache::open(cache_dir);
+    // 미검증 결과로 재스캔한 경우에도 이전(오탐일 수 있는) 캐시는 지운다.
+    cache.remove(&cache_exe_name(exe_path), analysis_cache::GWORLD);
+    if candidate == Candidate::Verified
+        && let Ok(image) = PeImage::read(backend, base)
+    {
+        let key = build_key(exe_path, image.timestamp, image.size_of_image);
+        cache.insert(&key, gworld_symbol(rva, Some(signature)));
+    }
+    Ok(rva)
+}
+
+/// 로드된 PE 헤더의 TimeDateStamp/SizeOfImage와 디스크 파일의 VERSIONINFO로 빌드를 식별한다.
+pub fn game_build<B: ProcessBackend>(backend: &B, base: u64, exe_path: &str) -> Option<GameBuild> {
+    let image = match PeImage::read(backend, base) {
+        Ok(image) => image,
+        Err(e) => {
+            log::warn!("PE 헤더를 읽지 못해 빌드를 식별하지 못했습니다: {:#}", e);
+            return None;
+        }
+    };
+    let version = (!exe_path.is_empty())
+        .then(|| pe_file_version(Path::new(exe_path)))
+        .flatten();
+    Some(GameBuild::from_pe(image.timestamp, image.size_of_image, version))
+}
+
+// ── .pdata 기반 GWorld RVA 스캔 ───────────────────────────────────────────────
+
+/// 시그니처를 우선순위 순서로 시도한다. 함수 영역은 한 번만 읽고,
+/// 일치마다 후보를 검증해 시그니처별 최선의 후보를 기록하다가 최우선 시그니처가 검증되면 즉시 끝낸다.
+/// 반환값: (GWorld RVA, 일치한 시그니처 이름, 검증 결과)
+fn scan_gworld_rva<B: ProcessBackend>(
+    backend: &B,
+    base: u64,
+    signatures: &[GWorldSignature],
+    variants: &[WuwaOffset],
+) -> Result<(u64, String, Candidate)> {
+    let signatures = compile_signatures(signatures);
+    if signatures.is_empty() {
+        bail!("사용 가능한 GWorld 시그니처가 없습니다.");
+    }
+
+    let image = PeImage::read(backend, base).context("PE 헤더 확인 실패")?;
+    let size_of_image = image.size_of_image;
+    let pdata_range = image.pdata().context(".pdata 섹션을 찾지 못했습니다.")?;
+    let entry_count = (pdata_range.end - pdata_range.start) as usize / 12;
+    if entry_count == 0 {
+        bail!(".pdata 섹션이 비어 있습니다.");
+    }
+
+    let mut pdata = vec![0u8; entry_count * 12];
+    if backend.read_bytes(base + pdata_range.start, &mut pdata).is_err() {
+        bail!(".pdata 읽기 실패");
+    }
+
+    // 시그니처별 최선의 후보 (Unverified는 첫 번째 것을 유지)
+    let mut found: Vec<Option<(u64, Candidate)>> = vec![None; signatures.len()];
+    // 같은 RVA를 참조하는 명령어가 많으므로 검증 결과를 재사용한다.
+    let mut checked: HashMap<u64, Candidate> = HashMap::new();
+    let verified = |f: &Option<(u64, Candidate)>| f.is_some_and(|(_, c)| c == Candidate::Verified);
+
+    let mut funcs: Vec<(u32, u32)> = (0..entry_count)
+        .map(|i| {
+            let b = u32::from_le_bytes(pdata[i * 12..i * 12 + 4].try_into().unwrap());
+            let e = u32::from_le_bytes(pdata[i * 12 + 4..i * 12 + 8].try_into().unwrap());
+            (b, e)
+        })
+        .filter(|&(b, e)| b > 0 && e > b && image.is_executable(b as u64..e as u64))
+        .collect();
+    funcs.sort_unstable_by_key(|&(b, _)| b);
+
+    log::info!("GWorld 스캔: .pdata 함수 {} 개 (실행 섹션 밖 {} 개 제외)", funcs.len(), entry_count - funcs.len());
+    log::debug!("PE 섹션: .text {:X?}, .data {:X?}, .pdata {:X?}", image.text(), image.data(), pdata_range);
+
+    let mut i = 0;
+    while i < funcs.len() {
+        let batch_start = funcs[i].0 as u64;
+        let mut batch_end = funcs[i].1 as u64;
+        let mut j = i + 1;
+
+        while j < funcs.len() {
+            let gap = funcs[j].0 as u64 - batch_end;
+            let new_end = funcs[j].1 as u64;
+            if gap > BATCH_GAP || new_end - batch_start > MAX_BATCH_SIZE as u64 {
+                break;
+            }
+            batch_end = new_end;
+            j += 1;
+        }
+
+        let read_size = (batch_end - batch_start) as usize;
+        let mut buf = vec![0u8; read_size];
+
+        if backend.read_bytes(base + batch_start, &mut buf).is_ok() {
+            for (k, sig) in signatures.iter().enumerate() {
+                if verified(&found[k]) {
+                    continue;
+                }
+                for off in sig.pattern.find_iter(&buf) {
+                    let gworld_rva = sig.target_rva(&buf, off, batch_start);
+                    // GWorld는 전역 변수이므로 쓰기 가능한 데이터 섹션에 있어야 한다.
+                    if !image.is_writable_data(gworld_rva) {
+                        continue;
+                    }
+                    let candidate = *checked.entry(gworld_rva).or_insert_with(|| {
+                        check_candidate(backend, base, size_of_image, gworld_rva, variants)
+                    });
+                    log::debug!(
+                        "GWorld 후보 [{}]: 0x{:X} (명령어 RVA 0x{:X}) → {:?}",
+                        sig.name, gworld_rva, batch_start + off as u64, candidate
+                    );
+                    if candidate > Candidate::Rejected && found[k].is_none_or(|(_, c)| candidate > c) {
+                        found[k] = Some((gworld_rva, candidate));
+                    }
+                    if verified(&found[k]) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if verified(&found[0]) {
+            break;
+        }
+        i = j;
+    }
+
+    // 검증된 후보를 우선하고, 같은 등급이면 우선순위가 높은 시그니처를 사용한다.
+    let best = found
+        .iter()
+        .enumerate()
+        .filter_map(|(k, f)| f.map(|(rva, c)| (k, rva, c)))
+        .max_by(|a, b| a.2.cmp(&b.2).then(b.0.cmp(&a.0)));
+    match best {
+        Some((k, rva, candidate)) => {
+            log::info!(
+                "GWorld RVA 발견 [{}]: 0x{:X} ({})",
+                signatures[k].name,
+                rva,
+                if candidate == Candidate::Verified { "검증됨" } else { "미검증, 캐시 안 함" }
+            );
+            if k > 0 {
+                log::warn!(
+                    "상위 GWorld 시그니처 불일치, '{}'(#{}) 사용",
+                    signatures[k].name, k + 1
+                );
+            }
+            Ok((rva, signatures[k].name.to_string(), candidate))
+        }
+        None if checked.is_empty() => bail!("GWorld 패턴을 찾지 못했습니다."),
+        None => bail!("GWorld 패턴 후보 {}개가 모두 검증에 실패했습니다.", checked.len()),
+    }
+}
+
+// ── 캐시 ─────────────────────────────────────────────────────────────────────
+
+fn find_gworld_rva_with_cache<B: ProcessBackend>(
+    backend: &B,
+    base: u64,
+    exe_path: &str,
+    cache_dir: &Path,
+    signatures: &[GWorldSignature],
+    variants: &[WuwaOffset],
+) -> Result<u64> {
+    let image = PeImage::read(backend, base).context("PE 헤더 확인 실패")?;
+    let key = build_key(exe_path, image.timestamp, image.size_of_image);
+
+    let mut cache = AnalysisCache::open(cache_dir);
+    if let Some(symbol) = cache.lookup(&key, analysis_cache::GWORLD) {
+        log::info!(
+            "캐시된 GWorld RVA 사용: 0x{:X} [{}]",
+            symbol.address,
+            symbol.source.as_deref().unwrap_or("-")
+        );
+        return Ok(symbol.address);
+    }
+
+    log::info!("GWorld RVA 캐시 미스 → .pdata 스캔 시작");
+    let (gworld_rva, signature, candidate) = scan_gworld_rva(backend, base, signatures, variants)?;
+
+    if candidate == Candidate::Verified {
+        cache.insert(&key, gworld_symbol(gworld_rva, Some(signature)));
+    }
+
+    Ok(gworld_rva)
+}
+
+// ── 오프라인 (디스크의 실행 파일) ────────────────────────────────────────────
+
+/// 게임을 실행하지 않고 디스크의 실행 파일에서 GWorld RVA를 구한다.
+/// 파일을 RVA 기준으로 읽는 백엔드를 만들어 실행 중 스캔과 같은 .pdata 스캔을 그대로 사용한다.
+/// 메모리 값이 없으므로 후보 검증은 불가능하며, 우선순위가 가장 높은 일치를 반환한다.
+pub fn scan_exe_file(path: &Path, scan_config: &GWorldScanConfig) -> Result<ExeScanResult> {
+    let image = PeFileImage::load(path)?;
+    let (rva, signature, _) = scan_gworld_rva(&image, 0, &scan_config.signatures(), &[])?;
+    let exe_path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    Ok(ExeScanResult {
+        exe_path: exe_path.to_string_lossy().into_owned(),
+        pe_timestamp: image.headers.timestamp,
+        size_of_image: image.headers.size_of_image,
+        gworld_rva: rva,
+        signature: Some(signature),
+    })
+}
+
+/// 오프라인 스캔 결과를 캐시에 미리 넣어 둔다. (같은 실행 파일의 다른 빌드 기록은 지움)
+/// 키가 파일 이름 + build_id이므로 실행 중 프로세스에서 얻은 경로와 형식이 달라도 적중한다.
+pub fn seed_cache(cache_dir: &Path, result: &ExeScanResult) {
+    let key = build_key(&result.exe_path, result.pe_timestamp, result.size_of_image);
+    AnalysisCache::open(cache_dir).insert(&key, gworld_symbol(result.gworld_rva, result.signature.clone()));
+}
+
+/// 디스크의 PE 파일을 로드된 이미지처럼 RVA로 읽는다. (module base = 0)
+struct PeFileImage {
+    bytes: Vec<u8>,
+    headers: PeImage,
+}
+
+impl PeFileImage {
+    fn load(path: &Path) -> Result<Self> {
+        let bytes = fs::read(path).with_context(|| format!("실행 파일을 읽지 못했습니다: {}", path.display()))?;
+        let headers = PeImage::parse(&bytes).context("PE 헤더 확인 실패")?;
+        Ok(Self { bytes, headers })
+    }
+
+    fn file_range(&self, rva: u64, len: usize) -> Option<(usize, usize)> {
+        let end = rva.checked_add(len as u64)?;
+        if end <= self.headers.size_of_headers as u64 {
+            return Some((rva as usize, len));
+        }
+        let section = self.headers.sections.iter().find(|s| {
+            let va = s.virtual_address as u64;
+            rva >= va && end <= va + s.virtual_size.max(s.size_of_raw_data) as u64
+        })?;
+        // 파일에 실리지 않은 꼬리(.bss 등)는 0으로 채운다.
+        let start = (rva - section.virtual_address as u64) as usize;
+        let available = (section.size_of_raw_data as usize).saturating_sub(start).min(len);
+        Some((section.pointer_to_raw_data as usize + start, available))
+    }
+}
+
+impl ProcessBackend for PeFileImage {
+    fn is_alive(&self) -> bool {
+        true
+    }
+
+    fn read_bytes(&self, address: u64, buffer: &mut [u8]) -> Result<(), NativeError> {
+        let range = self
+            .file_range(address, buffer.len())
+            .filter(|&(offset, len)| offset + len <= self.bytes.len());
+        let Some((offset, len)) = range else {
+            return Err(NativeError::value_read(
+                ReadFailure::at(address)
+                    .fault(ReadFault::Unmapped, None)
+                    .note(format!("파일에 매핑되지 않은 RVA (+{:X})", buffer.len())),
+            ));
+        };
+        buffer[..len].copy_from_slice(&self.bytes[offset..offset + len]);
+        buffer[len..].fill(0);
+        Ok(())
+    }
+
+    fn read_gworld(&self, offset: &WuwaOffset) -> Result<u64, NativeError> {
+        self.read_memory::<u64>(offset.global_gworld)
+    }
+
+    fn module_base(&self) -> u64 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pe_image::tests::{CODE, DATA, RDATA, pe_headers};
+    use crate::snapshot_proc::tests::{fixture_snapshot, variant};
+    use crate::snapshot_proc::{SnapshotProc, SnapshotRegion};
+
+    // 스냅샷 픽스처의 모듈 base와 UWorld. 픽스처의 UWorld는 레거시 체인 variant("current", 0x1B8)로 검증된다.
+    const BASE: u64 = 0x7FF6_1000_0000;
+    const UWORLD: u64 = 0x1_0000_1000;
+    const CODE_RVA: u32 = 0x1000;
+    const FUNCTION_SIZE: u32 = 0x20;
+
+    // .data의 전역 변수
+    const VERIFIED: u32 = 0x3000;
+    const NULL: u32 = 0x3008;
+    const NON_CANONICAL: u32 = 0x3010;
+    // vtable이 모듈 밖(NULL)인 객체
+    const NOT_UOBJECT: u32 = 0x3018;
+    const VERIFIED_2: u32 = 0x3020;
+    // .rdata에 있는 (쓰기 불가) UWorld 포인터
+    const READ_ONLY: u32 = 0x2008;
+
+    type Encode = fn(u32, u32) -> Vec<u8>;
+
+    /// mov rbx, [rip+disp32]; test rbx, rbx; je; mov r8b, 1 (레거시 prefix/suffix 기본값)
+    fn load_rbx(instr_rva: u32, target_rva: u32) -> Vec<u8> {
+        rip_relative(&[0x48, 0x8B, 0x1D], &[0x48, 0x85, 0xDB, 0x74, 0x05, 0x41, 0xB0, 0x01], instr_rva, target_rva)
+    }
+
+    /// mov rax, [rip+disp32]; test rax, rax
+    fn load_rax(instr_rva: u32, target_rva: u32) -> Vec<u8> {
+        rip_relative(&[0x48, 0x8B, 0x05], &[0x48, 0x85, 0xC0], instr_rva, target_rva)
+    }
+
+    fn rip_relative(opcode: &[u8], suffix: &[u8], instr_rva: u32, target_rva: u32) -> Vec<u8> {
+        let disp = target_rva as i64 - (instr_rva as i64 + opcode.len() as i64 + 4);
+        [opcode, &(disp as i32).to_le_bytes(), suffix].concat()
+    }
+
+    fn rax_signature() -> GWorldSignature {
+        GWorldSignature {
+            name: "rax".to_string(),
+            pattern: "48 8B 05 ?? ?? ?? ?? 48 85 C0".to_string(),
+            disp_offset: 3,
+            instr_len: 7,
+            arch: SignatureArch::X86_64,
+        }
+    }
+
+    fn default_signature() -> GWorldSignature {
+        GWorldScanConfig::default().signatures()[0].clone()
+    }
+
+    /// 스냅샷 픽스처에 PE 이미지를 더한다. 함수마다 (인코더, 참조할 전역 변수 RVA) 하나씩을
+    /// .text에 FUNCTION_SIZE 간격으로 두고 .pdata에 등록한다. UWorld의 vtable은 .rdata를 가리키게 한다.
+    fn image(functions: &[(Encode, u32)]) -> SnapshotProc {
+        let mut snapshot = fixture_snapshot();
+        let pdata_rva = 0x4000;
+        let pdata_end = pdata_rva + functions.len() as u32 * 12;
+        let headers = pe_headers(
+            0x6650_0000,
+            &[
+                (".text", CODE_RVA..CODE_RVA + 0x200, CODE),
+                (".rdata", 0x2000..0x2200, RDATA),
+                (".data", 0x3000..0x3100, DATA),
+                (".pdata", pdata_rva..pdata_end, RDATA),
+            ],
+        );
+
+        let mut code = vec![0xCCu8; 0x200];
+        let mut pdata = Vec::new();
+        for (i, (encode, target)) in functions.iter().enumerate() {
+            let start = CODE_RVA + i as u32 * FUNCTION_SIZE;
+            let bytes = encode(start, *target);
+            let at = (start - CODE_RVA) as usize;
+            code[at..at + bytes.len()].copy_from_slice(&bytes);
+            pdata.extend([start, start + bytes.len() as u32, 0].iter().flat_map(|v| v.to_le_bytes()));
+        }
+
+        let mut rdata = vec![0u8; 0x200];
+        rdata[8..16].copy_from_slice(&UWORLD.to_le_bytes());
+        let globals = [UWORLD, 0, 0xDEAD_0000_0000_0010, 0x1_0000_2000, UWORLD];
+        let data: Vec<u8> = globals.iter().flat_map(|v| v.to_le_bytes()).collect();
+
+        let uworld = snapshot.regions.iter_mut().find(|r| r.address == UWORLD).unwrap();
+        uworld.bytes[..8].copy_from_slice(&(BASE + 0x2100).to_le_bytes());
+        assert_eq!(snapshot.module_base, BASE);
+        snapshot.regions.extend([
+            SnapshotRegion { address: BASE, bytes: headers },
+            SnapshotRegion { address: BASE + CODE_RVA as u64, bytes: code },
+            SnapshotRegion { address: BASE + 0x2000, bytes: rdata },
+            SnapshotRegion { address: BASE + 0x3000, bytes: data },
+            SnapshotRegion { address: BASE + pdata_rva as u64, bytes: pdata },
+        ]);
+        SnapshotProc::new(snapshot)
+    }
+
+    fn scan(proc: &SnapshotProc, signatures: &[GWorldSignature]) -> Result<(u64, String, Candidate)> {
+        scan_gworld_rva(proc, BASE, signatures, &[variant("current", 0x1B8)])
+    }
+
+    fn temp_cache_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("wuma-tracker-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn cached_rva(proc: &SnapshotProc, cache_dir: &Path) -> Option<u64> {
+        let image = PeImage::read(proc, BASE).unwrap();
+        let key = build_key("Client-Win64-Shipping.exe", image.timestamp, image.size_of_image);
+        AnalysisCache::open(cache_dir).lookup(&key, analysis_cache::GWORLD).map(|s| s.address)
+    }
+
+    #[test]
+    fn compile_rejects_signatures_without_disp32() {
+        let signature = |pattern: &str, disp_offset, instr_len| GWorldSignature {
+            name: pattern.to_string(),
+            pattern: pattern.to_string(),
+            disp_offset,
+            instr_len,
+            arch: SignatureArch::X86_64,
+        };
+        let arm64 = GWorldSignature { arch: SignatureArch::Arm64, ..rax_signature() };
+        let signatures = [
+            signature("48 8B 1D ?? ?? ?? ??", 3, 7),
+            // disp32가 패턴 밖으로 나감
+            signature("48 8B 1D ?? ??", 3, 7),
+            // 명령어가 disp32 전에 끝남
+            signature("48 8B 1D ?? ?? ?? ??", 3, 6),
+            signature("48 8B 1D ?? ?? ?? ?X", 3, 7),
+            arm64,
+        ];
+        let compiled = compile_signatures(&signatures);
+        assert_eq!(compiled.len(), 1);
+        assert_eq!(compiled[0].name, "48 8B 1D ?? ?? ?? ??");
+    }
+
+    #[test]
+    fn earlier_signature_wins() {
+        // 코드에서는 rbx 쪽이 먼저 나오지만 우선순위는 시그니처 목록 순서를 따른다.
+        let proc = image(&[(load_rbx, VERIFIED_2), (load_rax, VERIFIED)]);
+
+        let (rva, name, candidate) = scan(&proc, &[rax_signature(), default_signature()]).unwrap();
+        assert_eq!((rva, name.as_str(), candidate), (VERIFIED as u64, "rax", Candidate::Verified));
+
+        let (rva, name, _) = scan(&proc, &[default_signature(), rax_signature()]).unwrap();
+        assert_eq!((rva, name.as_str()), (VERIFIED_2 as u64, "default"));
+    }
+
+    #[test]
+    fn cache_key_ignores_path_form() {
+        let windows = build_key(r"\\?\C:\Wuthering Waves\Client\Binaries\Win64\Client-Win64-Shipping.exe", 0x6650_0000, 0x0A00_0000);
+        let wine = build_key("/home/user/.wine/drive_c/Wuthering Waves/Client/Binaries/Win64/client-win64-shipping.exe", 0x6650_0000, 0x0A00_0000);
+        let offline = build_key("Client-Win64-Shipping.exe", 0x6650_0000, 0x0A00_0000);
+        assert_eq!(windows, wine);
+        assert_eq!(windows, offline);
+        assert_ne!(windows, build_key("Client-Win64-Shipping.exe", 0x6651_0000, 0x0A00_0000));
+    }
+
+    #[test]
+    fn classifies_candidates() {
+        let proc = image(&[]);
+        let check = |rva: u32| check_candidate(&proc, BASE, 0x5000, rva as u64, &[variant("current", 0x1B8)]);
+        assert_eq!(check(VERIFIED), Candidate::Verified);
+        assert_eq!(check(NULL), Candidate::Unverified);
+        assert_eq!(check(NON_CANONICAL), Candidate::Rejected);
+        assert_eq!(check(NOT_UOBJECT), Candidate::Rejected);
+        // 읽을 수 없는 주소
+        assert_eq!(check(0x4800), Candidate::Rejected);
+        // 체인이 끊긴 UWorld는 사용하되 검증되지 않은 것으로 본다.
+        assert_eq!(
+            check_candidate(&proc, BASE, 0x5000, VERIFIED as u64, &[variant("previous", 0x180)]),
+            Candidate::Unverified
+        );
+    }
+
+    #[test]
+    fn ignores_targets_outside_writable_data() {
+        // .rdata의 UWorld 포인터와 .text 안을 가리키는 일치는 검증 전에 버려진다.
+        let proc = image(&[(load_rbx, READ_ONLY), (load_rbx, CODE_RVA)]);
+        let err = scan(&proc, &[default_signature()]).unwrap_err();
+        assert!(err.to_string().contains("찾지 못했습니다"), "{}", err);
+    }
+
+    #[test]
+    fn fails_when_every_candidate_is_rejected() {
+        let proc = image(&[(load_rbx, NON_CANONICAL), (load_rbx, NOT_UOBJECT)]);
+        let err = scan(&proc, &[default_signature()]).unwrap_err();
+        assert!(err.to_string().contains("2개가 모두 검증에 실패"), "{}", err);
+    }
+
+    #[test]
+    fn prefers_verified_over_unverified() {
+        // 같은 시그니처 안에서 나중에 나온 검증된 후보가 앞선 미검증 후보를 이긴다.
+        let proc = image(&[(load_rbx, NULL), (load_rbx, NON_CANONICAL), (load_rbx, VERIFIED)]);
+        let (rva, _, candidate) = scan(&proc, &[default_signature()]).unwrap();
+        assert_eq!((rva, candidate), (VERIFIED as u64, Candidate::Verified));
+
+        // 우선순위가 낮은 시그니처라도 검증되었으면 미검증인 상위 시그니처보다 앞선다.
+        let proc = image(&[(load_rax, NULL), (load_rbx, VERIFIED)]);
+        let (rva, name, _) = scan(&proc, &[rax_signature(), default_signature()]).unwrap();
+        assert_eq!((rva, name.as_str()), (VERIFIED as u64, "default"));
+
+        // 검증된 후보가 없으면 미검증 후보를 쓴다.
+        let proc = image(&[(load_rbx, NON_CANONICAL), (load_rbx, NULL)]);
+        let (rva, _, candidate) = scan(&proc, &[default_signature()]).unwrap();
+        assert_eq!((rva, candidate), (NULL as u64, Candidate::Unverified));
+    }
+
+    #[test]
+    fn caches_only_verified_results() {
+        let cache_dir = temp_cache_dir("pe-scan-cache");
+        let variants = [variant("current", 0x1B8)];
+        let signatures = [default_signature()];
+        let find = |proc: &SnapshotProc| {
+            find_gworld_rva_with_cache(proc, BASE, "Client-Win64-Shipping.exe", &cache_dir, &signatures, &variants)
+        };
+
+        let rejected = image(&[(load_rbx, NON_CANONICAL), (load_rbx, NOT_UOBJECT)]);
+        assert!(find(&rejected).is_err());
+        assert_eq!(cached_rva(&rejected, &cache_dir), None);
+
+        let unverified = image(&[(load_rbx, NON_CANONICAL), (load_rbx, NULL)]);
+        assert_eq!(find(&unverified).unwrap(), NULL as u64);
+        assert_eq!(cached_rva(&unverified, &cache_dir), None);
+
+        let verified = image(&[(load_rbx, NON_CANONICAL), (load_rbx, VERIFIED)]);
+        assert_eq!(find(&verified).unwrap(), VERIFIED as u64);
+        assert_eq!(cached_rva(&verified, &cache_dir), Some(VERIFIED as u64));
+
+        // 재스캔 결과가 미검증이면 이전 캐시도 지운다.
+        let scan_config = GWorldScanConfig::default();
+        let rva = rescan_gworld_rva(&unverified, BASE, "Client-Win64-Shipping.exe", &cache_dir, &scan_config, &variants);
+        assert_eq!(rva.unwrap(), NULL as u64);
+        assert_eq!(cached_rva(&verified, &cache_dir), None);
+
+        let _ = fs::remove_dir_all(&cache_dir);
+    }
+}
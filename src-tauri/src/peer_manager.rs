@@ -1,6 +1,6 @@
 use crate::types::{Peer, PlayerInfo, RtcSignal, SERVER_ID, SignalPacket, ManagedPeer};
 use anyhow::{anyhow, bail, Context, Result};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use webrtc::api::APIBuilder;
@@ -12,6 +12,10 @@ use webrtc::peer_connection::configuration::RTCConfiguration;
 pub(crate) struct PeerManager {
     peers: HashMap<String, ManagedPeer>,
     pm_sh_tx: mpsc::Sender<SignalPacket>,
+    // 현재 맵 이름과, 그 이름을 이미 전달받은 피어 목록
+    map_name: Option<String>,
+    map_synced: HashSet<String>,
+    requested_rates: HashMap<String, u32>,
 }
 
 impl PeerManager {
@@ -19,9 +23,18 @@ impl PeerManager {
         Self {
             peers: HashMap::new(),
             pm_sh_tx,
+            map_name: None,
+            map_synced: HashSet::new(),
+            requested_rates: HashMap::new(),
         }
     }
 
+    /// 맵이 바뀌면 모든 피어에게 다음 브로드캐스트 때 map-changed를 다시 보낸다.
+    pub fn set_map_name(&mut self, map_name: String) {
+        self.map_name = Some(map_name);
+        self.map_synced.clear();
+    }
+
     pub async fn handle_signaling_message(&mut self, message: SignalPacket) -> Result<()> {
         let client_id = message.from;
         // 메시지를 처리할 대상 Peer를 찾습니다.
@@ -141,6 +154,8 @@ impl PeerManager {
     }
 
     pub async fn handle_client_disconnect(&mut self, client_id: String) -> Result<()> {
+        self.map_synced.remove(&client_id);
+        self.requested_rates.remove(&client_id);
         if let Some(m_peer) = self.peers.remove(&client_id) {
             if let ManagedPeer::External(peer) = m_peer {
                 // PeerConnection을 정상적으로 종료하여 관련 리소스를 모두 해제합니다.
@@ -161,13 +176,31 @@ impl PeerManager {
         Ok(())
     }
 
-    pub async fn broadcast_data(&self, message: &PlayerInfo) -> Result<()> {
+    pub async fn broadcast_data(&mut self, message: &PlayerInfo) -> Result<()> {
         let payload = serde_json::to_string(message)
             .context("DataChannel send error: could not serialize data")?;
+        // 새로 연결된 피어도 맵 이름을 알 수 있도록, 아직 받지 못한 피어에게 위치보다 먼저 보낸다.
+        let map_name = self.map_name.clone();
+        let map_payload = map_name
+            .as_ref()
+            .map(|name| serde_json::to_string(&RtcSignal::MapChanged(name.clone())))
+            .transpose()
+            .context("DataChannel send error: could not serialize map name")?;
 
         for (client_id, peer) in &self.peers {
+            let pending_map = map_name.as_ref().filter(|_| !self.map_synced.contains(client_id));
             match peer {
                 ManagedPeer::Local => {
+                    if let Some(name) = pending_map {
+                        let packet = SignalPacket {
+                            from: SERVER_ID.to_string(),
+                            to: client_id.clone(),
+                            msg: RtcSignal::MapChanged(name.clone()),
+                        };
+                        if self.pm_sh_tx.send(packet).await.is_ok() {
+                            self.map_synced.insert(client_id.clone());
+                        }
+                    }
                     let packet = SignalPacket {
                         from: SERVER_ID.to_string(),
                         to: client_id.clone(),
@@ -179,6 +212,11 @@ impl PeerManager {
                 }
                 ManagedPeer::External(peer) => {
                     if peer.data_channel.ready_state() == RTCDataChannelState::Open {
+                        if let (Some(_), Some(map_payload)) = (pending_map, &map_payload) {
+                            if peer.data_channel.send_text(map_payload).await.is_ok() {
+                                self.map_synced.insert(client_id.clone());
+                            }
+                        }
                         if let Err(e) = peer.data_channel.send_text(&payload).await {
                             log::warn!(
                                 "[{}] DataChannel send error, but continuing broadcast: {}",
@@ -197,4 +235,16 @@ impl PeerManager {
     pub fn peer_count(&self) -> usize {
         self.peers.len()
     }
+
+    pub fn set_requested_sample_rate(&mut self, client_id: String, hz: u32) {
+        if self.peers.contains_key(&client_id) {
+            log::info!("[{}] Requested sample rate: {} Hz", client_id, hz);
+            self.requested_rates.insert(client_id, hz);
+        }
+    }
+
+    /// 피어들이 요청한 샘플링 Hz 중 가장 높은 값
+    pub fn requested_sample_rate(&self) -> Option<u32> {
+        self.requested_rates.values().copied().max()
+    }
 }
@@ -1,14 +1,28 @@
-use crate::offsets::WuwaOffset;
-use crate::types::NativeError::{PointerChainError, ValueReadError};
-use crate::types::{FIntVector, FTransformDouble, NativeError, PlayerInfo};
+use crate::analysis_cache::BuildKey;
+use crate::game_version::GameBuild;
+use crate::offsets::{ChainStep, ChainValue, GWorldScanConfig, TransformField, TransformLayout, TransformScalar, WuwaOffset};
+use crate::types::{
+    FIntVector, FTransform, MemoryRegion, NativeError, PageInfo, PageState, PlayerInfo, PtrClass, ReadFailure,
+};
+use crate::variant_stats::VariantStats;
 use std::f32::consts::PI;
 use std::mem::{self, MaybeUninit};
+use std::ops::Range;
 
 pub trait ProcessBackend {
     fn is_alive(&self) -> bool;
     fn read_bytes(&self, address: u64, buffer: &mut [u8]) -> Result<(), NativeError>;
     fn read_gworld(&self, offset: &WuwaOffset) -> Result<u64, NativeError>;
-    fn rescan_gworld(&mut self) {}
+    /// GWorld를 다시 찾는다. 후보 검증에 variants의 포인터 체인을 사용한다.
+    fn rescan_gworld(&mut self, _variants: &[WuwaOffset]) {}
+    /// GWorld 스캔 설정을 교체한다. 다음 rescan_gworld부터 적용되며, 바뀌었으면 true를 반환한다.
+    fn set_scan_config(&mut self, _scan_config: GWorldScanConfig) -> bool { false }
+
+    /// 게임 실행 파일(메인 모듈)의 로드 주소.
+    fn module_base(&self) -> u64;
+
+    /// 스캔/심볼로 찾은 GWorld RVA. None이면 오프셋의 global_gworld로 폴백한다.
+    fn gworld_rva(&self) -> Option<u64> { None }
 
     /// GWorld를 정상적으로 찾은 상태인지 반환한다.
     /// Windows: 초기 스캔 성공 여부. macOS: 항상 true (심볼 테이블 기반).
@@ -18,9 +32,40 @@ pub trait ProcessBackend {
         offset.name.clone()
     }
 
+    /// 연결된 게임의 빌드 정보. 알 수 없으면 None (모든 variant를 같은 우선순위로 시도)
+    fn game_build(&self) -> Option<&GameBuild> {
+        None
+    }
+
+    /// 분석 캐시/variant 통계 키 (실행 파일 + 빌드). 알 수 없으면 None (통계를 메모리에만 기록)
+    fn build_key(&self) -> Option<BuildKey> {
+        None
+    }
+
+    /// 프로세스의 메모리 영역 목록 (주소 오름차순). 조회할 수 없는 백엔드는 빈 목록
+    fn regions(&self) -> Vec<MemoryRegion> {
+        Vec::new()
+    }
+
+    /// 주소가 속한 영역. 매핑되지 않은 주소는 앞뒤 영역 사이의 Free 영역으로 반환한다.
+    fn query_region(&self, address: u64) -> Option<MemoryRegion> {
+        region_at(&self.regions(), address)
+    }
+
+    /// 주소가 속한 페이지 상태 (진단용). 조회할 수 없는 백엔드는 None
+    fn page_info(&self, address: u64) -> Option<PageInfo> {
+        self.query_region(address).map(|r| r.page_info())
+    }
+
+    /// 여러 영역을 한 번에 읽는다. 실패하면 (요청 인덱스, 에러)를 반환한다.
+    /// 일괄 읽기 syscall이 없는 백엔드(ReadProcessMemory, mach_vm_read_overwrite)는 차례로 읽는다.
+    fn read_many(&self, requests: &mut [(u64, &mut [u8])]) -> Result<(), (usize, NativeError)> {
+        read_each(self, requests)
+    }
+
     fn read_memory<T: Copy>(&self, address: u64) -> Result<T, NativeError> {
         if address == 0 {
-            return Err(PointerChainError { message: "addr=0".to_string() });
+            return Err(NativeError::pointer_chain(ReadFailure::at(0).pointer(PtrClass::Null)));
         }
 
         unsafe {
@@ -34,11 +79,54 @@ pub trait ProcessBackend {
     }
 }
 
+/// read_many의 기본 구현: 요청마다 read_bytes를 호출한다. (0 주소는 read_memory와 같은 NULL 에러)
+pub fn read_each<B: ProcessBackend + ?Sized>(
+    backend: &B,
+    requests: &mut [(u64, &mut [u8])],
+) -> Result<(), (usize, NativeError)> {
+    for (i, (address, buffer)) in requests.iter_mut().enumerate() {
+        if *address == 0 {
+            return Err((i, NativeError::pointer_chain(ReadFailure::at(0).pointer(PtrClass::Null))));
+        }
+        backend.read_bytes(*address, buffer).map_err(|e| (i, e))?;
+    }
+    Ok(())
+}
+
+/// 포인터 체인을 끝까지 따라간 결과.
+/// 샘플마다 트랜스폼, 월드 원점, PersistentLevel 슬롯을 read_many 한 번으로 다시 읽는다.
+#[derive(Clone, Copy)]
+pub struct ResolvedPlayer {
+    pub transform_addr: u64,
+    pub world_origin_addr: u64,
+    /// 월드 원점 체인의 마지막 포인터 슬롯(PersistentLevel)과 해석 당시의 값.
+    /// 값이 바뀌었으면 레벨이 교체된 것이므로 체인을 다시 해석해야 한다.
+    pub level_slot: Option<(u64, u64)>,
+    pub layout: TransformLayout,
+}
+
+impl ResolvedPlayer {
+    /// world_origin_trace: 월드 원점 체인을 resolve_chain_traced로 따라간 기록
+    pub fn new(
+        transform_addr: u64,
+        world_origin_addr: u64,
+        world_origin_trace: &[ChainTrace],
+        layout: TransformLayout,
+    ) -> Self {
+        let level_slot = world_origin_trace
+            .last()
+            .and_then(|t| Some((t.base.wrapping_add(t.field_offset), t.value?)));
+        Self { transform_addr, world_origin_addr, level_slot, layout }
+    }
+}
+
+/// stats가 있으면 이 빌드에서 성공했던 variant부터 시도하고, 각 시도 결과를 기록한다.
 pub fn select_player_info<B: ProcessBackend>(
     backend: &B,
     cached_offset: &mut Option<WuwaOffset>,
     offsets: &[WuwaOffset],
-) -> Result<PlayerInfo, NativeError> {
+    mut stats: Option<&mut VariantStats>,
+) -> Result<(PlayerInfo, ResolvedPlayer), NativeError> {
     if !backend.is_alive() {
         return Err(NativeError::ProcessTerminated);
     }
@@ -53,15 +141,28 @@ pub fn select_player_info<B: ProcessBackend>(
         *cached_offset = None;
     }
 
+    let build = backend.game_build();
     let mut first_err: Option<NativeError> = None;
-    for (i, offset) in offsets.iter().enumerate() {
-        match read_player_info(backend, offset) {
+    for (i, offset, fit) in order_variants(offsets, build, stats.as_deref()) {
+        let result = read_player_info(backend, offset);
+        if let Some(stats) = stats.as_deref_mut() {
+            stats.record(&offset.name, result.is_ok());
+        }
+        match result {
             Ok(location) => {
                 log::info!(
                     "Offset variant #{} ({}) succeeded.",
                     i + 1,
                     backend.active_offset_name(offset)
                 );
+                if fit == VariantFit::OtherVersion {
+                    log::warn!(
+                        "Offset variant #{} ({})의 game_versions에 현재 빌드 {}가 없습니다.",
+                        i + 1,
+                        offset.name,
+                        build.map(|b| b.to_string()).unwrap_or_default()
+                    );
+                }
                 *cached_offset = Some(offset.clone());
                 return Ok(location);
             }
@@ -79,95 +180,289 @@ pub fn select_player_info<B: ProcessBackend>(
         }
     }
 
+    // 이 빌드용(또는 버전 무관) variant가 하나도 없으면 포인터 체인 에러 대신 버전 미지원으로 보고한다.
+    if let Some(build) = build
+        && !offsets.is_empty()
+        && offsets.iter().all(|o| variant_fit(o, Some(build)) == VariantFit::OtherVersion)
+    {
+        return Err(NativeError::UnsupportedGameVersion { build: build.to_string() });
+    }
+
     // 모든 variant 실패 시, 첫 variant의 실제 실패 원인을 그대로 노출한다.
     // (실패 단계 = GWorld(.data) read 인지, 이후 포인터 체인인지 + OS 에러 코드 포함)
-    Err(first_err.unwrap_or(PointerChainError {
-        message: "사용 가능한 버전 값을 찾지 못했습니다.".to_string(),
-    }))
+    Err(first_err.unwrap_or(NativeError::OffsetsNotLoaded))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum VariantFit {
+    /// game_versions에 현재 빌드가 있음
+    Matching,
+    /// game_versions가 비어 있음 (버전 무관)
+    Untagged,
+    /// 다른 빌드용 (태그가 누락된 경우를 대비해 마지막에 시도)
+    OtherVersion,
+}
+
+fn variant_fit(offset: &WuwaOffset, build: Option<&GameBuild>) -> VariantFit {
+    if offset.game_versions.is_empty() {
+        return VariantFit::Untagged;
+    }
+    match build {
+        Some(build) if offset.game_versions.iter().any(|tag| build.matches(tag)) => VariantFit::Matching,
+        Some(_) => VariantFit::OtherVersion,
+        // 빌드를 알 수 없으면 태그로 판단하지 않는다.
+        None => VariantFit::Untagged,
+    }
+}
+
+/// (원래 인덱스, variant, 적합도)를 적합도 → 성공 기록 순으로 정렬한다. 나머지는 원래 순서를 유지한다.
+fn order_variants<'a>(
+    offsets: &'a [WuwaOffset],
+    build: Option<&GameBuild>,
+    stats: Option<&VariantStats>,
+) -> Vec<(usize, &'a WuwaOffset, VariantFit)> {
+    let mut ordered: Vec<_> = offsets
+        .iter()
+        .enumerate()
+        .map(|(i, offset)| (i, offset, variant_fit(offset, build)))
+        .collect();
+    ordered.sort_by_key(|&(_, offset, fit)| (fit, stats.map_or(0, |s| s.rank(&offset.name))));
+    ordered
 }
 
 fn read_player_info<B: ProcessBackend>(
     backend: &B,
     offset: &WuwaOffset,
-) -> Result<PlayerInfo, NativeError> {
+) -> Result<(PlayerInfo, ResolvedPlayer), NativeError> {
     let gworld = backend.read_gworld(offset)?;
+    read_player_info_from(backend, gworld, offset)
+}
+
+/// GWorld 스캔 후보 검증용: 주어진 UWorld에서 variant 중 하나라도 체인 전체가 성공하는지 확인한다.
+pub fn probe_player_chain<B: ProcessBackend>(backend: &B, gworld: u64, variants: &[WuwaOffset]) -> bool {
+    variants
+        .iter()
+        .any(|offset| read_player_info_from(backend, gworld, offset).is_ok())
+}
+
+/// UObject처럼 보이는지 확인한다: 정상 범위의 정렬된 포인터이고, 첫 8바이트(vtable)가 모듈 이미지 안을 가리킨다.
+pub fn looks_like_uobject<B: ProcessBackend>(backend: &B, ptr: u64, image: Range<u64>) -> bool {
+    if classify_ptr(ptr) != PtrClass::Ok {
+        return false;
+    }
+    backend
+        .read_memory::<u64>(ptr)
+        .is_ok_and(|vtable| image.contains(&vtable))
+}
+
+fn read_player_info_from<B: ProcessBackend>(
+    backend: &B,
+    gworld: u64,
+    offset: &WuwaOffset,
+) -> Result<(PlayerInfo, ResolvedPlayer), NativeError> {
+    let chain = offset.player_chain();
+    let layout = offset.transform_layout();
+
+    let transform_addr = resolve_chain(backend, gworld, &chain.transform, ChainValue::Transform)?;
+    let mut world_origin_trace = Vec::new();
+    let world_origin_addr = resolve_chain_traced(
+        backend,
+        gworld,
+        &chain.world_origin,
+        ChainValue::IntVector,
+        Some(&mut world_origin_trace),
+    )?;
+
+    let resolved = ResolvedPlayer::new(transform_addr, world_origin_addr, &world_origin_trace, layout);
+    Ok((read_resolved_player(backend, &resolved)?, resolved))
+}
 
-    let targets = [
-        ("OwningGameInstance", offset.uworld_owninggameinstance),
-        ("TArray<*LocalPlayers>", offset.ugameinstance_localplayers),
-        ("LocalPlayer", 0),
-        ("PlayerController", offset.uplayer_playercontroller),
-        ("APawn", offset.aplayercontroller_acknowlegedpawn),
-        ("RootComponent", offset.aactor_rootcomponent),
+/// 이미 해석된 주소에서 트랜스폼과 월드 원점을 다시 읽는다. (샘플마다 read_many 1회)
+/// 체인이 끊겨 해제된 메모리를 읽는 경우를 걸러내기 위해 값의 타당성과 레벨 교체 여부도 확인한다.
+pub fn read_resolved_player<B: ProcessBackend>(
+    backend: &B,
+    resolved: &ResolvedPlayer,
+) -> Result<PlayerInfo, NativeError> {
+    const STAGES: [&str; 3] = ["ftrans", "worigin", "PersistentLevel"];
+    let mut transform = vec![0u8; resolved.layout.byte_len()];
+    let mut world_origin = [0u8; 12];
+    let mut level = [0u8; 8];
+    let mut requests: Vec<(u64, &mut [u8])> = vec![
+        (resolved.transform_addr, &mut transform),
+        (resolved.world_origin_addr, &mut world_origin),
     ];
+    if let Some((slot, _)) = resolved.level_slot {
+        requests.push((slot, &mut level));
+    }
+    backend.read_many(&mut requests).map_err(|(i, e)| e.in_value(STAGES[i]))?;
 
-    let mut last_addr = gworld;
-    for (name, field_offset) in targets {
-        let target = last_addr + field_offset;
-        match backend.read_memory::<u64>(target) {
-            Ok(v) => {
-                last_addr = v;
-            }
-            Err(e) => {
-                return Err(PointerChainError {
-                    message: format!(
-                        "chain['{}' {:X}+{:X} {}]: {}",
-                        name, last_addr, field_offset, classify_ptr(last_addr), e
-                    ),
-                });
-            }
+    if let Some((slot, expected)) = resolved.level_slot {
+        let current = u64::from_le_bytes(level);
+        if current != expected {
+            return Err(NativeError::pointer_chain(
+                ReadFailure::at(slot)
+                    .stage("PersistentLevel")
+                    .note(format!("레벨 교체됨 ({:X} → {:X})", expected, current)),
+            ));
         }
     }
 
-    let transform_addr = last_addr + offset.uscenecomponent_componenttoworld;
-    let location = backend
-        .read_memory::<FTransformDouble>(transform_addr)
-        .map_err(|e| ValueReadError {
-            message: format!("ftrans@{:X}: {}", transform_addr, e),
-        })?;
-
-    let (roll, pitch, yaw) = quat_to_euler(
-        location.rot_x,
-        location.rot_y,
-        location.rot_z,
-        location.rot_w,
-    );
-
-    let persistent_level_addr = gworld + offset.uworld_persistentlevel;
-    let persistent_level = backend
-        .read_memory::<u64>(persistent_level_addr)
-        .map_err(|e| PointerChainError {
-            message: format!("plevel@{:X}: {}", persistent_level_addr, e),
-        })?;
-
-    let world_origin_addr = persistent_level + offset.ulevel_lastworldorigin;
-    let root_location = backend
-        .read_memory::<FIntVector>(world_origin_addr)
-        .map_err(|e| ValueReadError {
-            message: format!("worigin@{:X}: {}", world_origin_addr, e),
-        })?;
+    let location = decode_transform(&resolved.layout, &transform);
+    let root_location = decode_int_vector(&world_origin);
+    if !is_plausible_transform(&location) {
+        return Err(NativeError::value_read(
+            ReadFailure::at(resolved.transform_addr).stage("ftrans").note("비정상 트랜스폼 값"),
+        ));
+    }
 
+    let [rot_x, rot_y, rot_z, rot_w] = location.rotation.map(|v| v as f32);
+    let (roll, pitch, yaw) = quat_to_euler(rot_x, rot_y, rot_z, rot_w);
+
+    let [loc_x, loc_y, loc_z] = location.translation;
     Ok(PlayerInfo {
-        x: location.loc_x + (root_location.x as f32),
-        y: location.loc_y + (root_location.y as f32),
-        z: location.loc_z + (root_location.z as f32),
+        x: loc_x + root_location.x as f64,
+        y: loc_y + root_location.y as f64,
+        z: loc_z + root_location.z as f64,
         pitch,
         yaw,
         roll,
     })
 }
 
+/// bytes는 layout.byte_len() 길이여야 한다.
+fn decode_transform(layout: &TransformLayout, bytes: &[u8]) -> FTransform {
+    let size = layout.scalar_size();
+    let component = |field: TransformField, i: usize| {
+        let at = layout.field_offset(field) + i * size;
+        match layout.scalar() {
+            TransformScalar::F32 => f32::from_le_bytes(bytes[at..at + 4].try_into().unwrap()) as f64,
+            TransformScalar::F64 => f64::from_le_bytes(bytes[at..at + 8].try_into().unwrap()),
+        }
+    };
+    FTransform {
+        rotation: std::array::from_fn(|i| component(TransformField::Rotation, i)),
+        translation: std::array::from_fn(|i| component(TransformField::Translation, i)),
+        scale: std::array::from_fn(|i| component(TransformField::Scale, i)),
+    }
+}
+
+fn decode_int_vector(bytes: &[u8; 12]) -> FIntVector {
+    let at = |i: usize| i32::from_le_bytes(bytes[i * 4..i * 4 + 4].try_into().unwrap());
+    FIntVector { x: at(0), y: at(1), z: at(2) }
+}
+
+/// 회전 쿼터니언은 단위 길이여야 하고, 위치는 유한한 월드 범위 안이어야 한다.
+fn is_plausible_transform(t: &FTransform) -> bool {
+    const MAX_WORLD_COORD: f64 = 1.0e8;
+    let norm: f64 = t.rotation.iter().map(|v| v * v).sum();
+    (norm - 1.0).abs() < 0.01
+        && t.translation
+            .iter()
+            .all(|v| v.is_finite() && v.abs() < MAX_WORLD_COORD)
+}
+
+/// UWorld 주소에서 시작해 체인을 따라가고, 마지막 Read 단계가 가리키는 주소를 반환한다.
+/// Add/Index는 필드 오프셋으로 누적했다가 Deref 시점에 한 번에 읽는다. (에러 메시지에 base+offset 표시)
+pub fn resolve_chain<B: ProcessBackend>(
+    backend: &B,
+    start: u64,
+    steps: &[ChainStep],
+    expect: ChainValue,
+) -> Result<u64, NativeError> {
+    resolve_chain_traced(backend, start, steps, expect, None)
+}
+
+/// resolve_chain과 같지만, trace가 있으면 Deref 단계마다 읽은 주소와 값을 남긴다. (진단 리포트용)
+pub fn resolve_chain_traced<B: ProcessBackend>(
+    backend: &B,
+    start: u64,
+    steps: &[ChainStep],
+    expect: ChainValue,
+    mut trace: Option<&mut Vec<ChainTrace>>,
+) -> Result<u64, NativeError> {
+    let mut base = start;
+    let mut field_offset = 0u64;
+    for (i, step) in steps.iter().enumerate() {
+        match step {
+            ChainStep::Add { value } => field_offset = field_offset.wrapping_add(*value),
+            ChainStep::Index { index, stride } => {
+                field_offset = field_offset.wrapping_add(index.wrapping_mul(*stride))
+            }
+            ChainStep::Deref { name } => {
+                let result = backend.read_memory::<u64>(base.wrapping_add(field_offset));
+                if let Some(trace) = trace.as_deref_mut() {
+                    trace.push(ChainTrace {
+                        name: name.clone(),
+                        base,
+                        field_offset,
+                        value: result.as_ref().ok().copied(),
+                    });
+                }
+                match result {
+                    Ok(v) => {
+                        base = v;
+                        field_offset = 0;
+                    }
+                    Err(e) => return Err(e.in_chain(name, base, field_offset, classify_ptr(base))),
+                }
+            }
+            ChainStep::Read { value } => {
+                if *value != expect || i + 1 != steps.len() {
+                    return Err(NativeError::pointer_chain(ReadFailure::at(base).stage("chain").note(format!(
+                        "잘못된 Read 단계 #{} ({:?}, 기대값 {:?})",
+                        i + 1,
+                        value,
+                        expect
+                    ))));
+                }
+                return Ok(base.wrapping_add(field_offset));
+            }
+        }
+    }
+
+    Err(NativeError::pointer_chain(
+        ReadFailure::at(base).stage("chain").note(format!("{:?} Read 단계가 없습니다.", expect)),
+    ))
+}
+
+/// resolve_chain_traced가 남기는 Deref 단계 기록. value가 None이면 이 단계에서 읽기에 실패했다.
+pub struct ChainTrace {
+    pub name: String,
+    pub base: u64,
+    pub field_offset: u64,
+    pub value: Option<u64>,
+}
+
 /// 포인터 값의 타당성을 분류해 실패 원인 진단을 돕는다.
 /// - NULL/거의NULL: 게임 상태 문제(아직 월드 미진입, 폰 없음 등) 또는 오프셋이 null 슬롯을 가리킴
 /// - 비정상범위: 오프셋/버전 불일치로 엉뚱한 값을 따라감, 또는 ACE의 포인터 암호화/셔플 의심
 /// - 정상범위인데 읽기 실패: 페이지 보호/해제 의심
-fn classify_ptr(p: u64) -> &'static str {
+pub fn classify_ptr(p: u64) -> PtrClass {
     const USERMODE_MAX: u64 = 0x0000_7FFF_FFFF_FFFF;
-    if p == 0 { "NULL" }
-    else if p < 0x1_0000 { "~NULL" }
-    else if p > USERMODE_MAX { "!canon" }
-    else if p & 0xF != 0 { "!align" }
-    else { "ok" }
+    if p == 0 { PtrClass::Null }
+    else if p < 0x1_0000 { PtrClass::NearNull }
+    else if p > USERMODE_MAX { PtrClass::NonCanonical }
+    else if p & 0xF != 0 { PtrClass::Misaligned }
+    else { PtrClass::Ok }
+}
+
+/// 영역 목록에서 address를 포함하는 영역을 찾는다. 목록 사이의 빈 공간은 Free 영역으로 만든다.
+pub fn region_at(regions: &[MemoryRegion], address: u64) -> Option<MemoryRegion> {
+    if regions.is_empty() {
+        return None;
+    }
+    if let Some(region) = regions.iter().find(|r| r.contains(address)) {
+        return Some(region.clone());
+    }
+    let base = regions.iter().map(|r| r.end()).filter(|&end| end <= address).max().unwrap_or(0);
+    let end = regions.iter().map(|r| r.base).filter(|&b| b > address).min().unwrap_or(u64::MAX);
+    Some(MemoryRegion {
+        base,
+        size: end - base,
+        state: PageState::Free,
+        protection: Default::default(),
+        module: None,
+    })
 }
 
 fn quat_to_euler(x: f32, y: f32, z: f32, w: f32) -> (f32, f32, f32) {
@@ -188,3 +483,48 @@ fn quat_to_euler(x: f32, y: f32, z: f32, w: f32) -> (f32, f32, f32) {
 
     (roll * 180.0 / PI, pitch * 180.0 / PI, yaw * 180.0 / PI)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offset(name: &str, game_versions: &[&str]) -> WuwaOffset {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "global_gworld": 0,
+            "game_versions": game_versions,
+        }))
+        .unwrap()
+    }
+
+    fn names(ordered: &[(usize, &WuwaOffset, VariantFit)]) -> Vec<String> {
+        ordered.iter().map(|(_, o, _)| o.name.clone()).collect()
+    }
+
+    #[test]
+    fn orders_by_fit_before_success_record() {
+        let offsets = [
+            offset("old", &["2.3"]),
+            offset("generic", &[]),
+            offset("matching", &["2.4"]),
+            offset("generic-2", &[]),
+        ];
+        let build = GameBuild::from_pe(0x6650_0000, 0x0A00_0000, Some("2.4.1.0".to_string()));
+        let mut stats = VariantStats::in_memory();
+        stats.record("matching", false);
+        stats.record("generic-2", true);
+        stats.record("old", true);
+
+        // 다른 빌드용 variant는 마지막 성공이어도 뒤로, 맞는 빌드의 variant는 실패만 했어도 앞으로 간다.
+        let ordered = order_variants(&offsets, Some(&build), Some(&stats));
+        assert_eq!(names(&ordered), ["matching", "generic-2", "generic", "old"]);
+
+        // 기록이 없으면 같은 적합도 안에서는 원래 순서를 유지한다.
+        let ordered = order_variants(&offsets, Some(&build), None);
+        assert_eq!(names(&ordered), ["matching", "generic", "generic-2", "old"]);
+
+        // 빌드를 모르면 태그를 보지 않고 성공 기록만으로 정한다.
+        let ordered = order_variants(&offsets, None, Some(&stats));
+        assert_eq!(names(&ordered), ["old", "generic-2", "generic", "matching"]);
+    }
+}
@@ -1,14 +1,16 @@
-use crate::native_collector::{NativeCollector, collection_loop};
-use crate::offsets::TrackerConfig;
+use crate::native_collector::{NativeCollector, SampleRateControl, collection_loop};
+use crate::offsets::{GWorldScanConfig, TrackerConfig, WuwaOffset};
 use crate::peer_manager::PeerManager;
 use crate::room_code_generator::generate_room_code_base36;
 use crate::signaling_handler::SignalingHandler;
 use crate::types::{CollectorMessage, GlobalState, RtcSignal, SignalPacket, SupervisorCommand};
 use crate::util;
+use crate::TauriState;
 use anyhow::Result;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, Emitter, Manager};
-use tokio::sync::{Mutex, mpsc, oneshot};
+use tokio::sync::{Mutex, mpsc, oneshot, watch};
 use tokio_util::sync::CancellationToken;
 
 struct CollectorState {
@@ -20,20 +22,22 @@ pub struct RtcSupervisor {
     signaling_handler: SignalingHandler,
     peer_manager: PeerManager,
     collector_state: CollectorState,
-    offsets: Arc<Mutex<Option<TrackerConfig>>>,
+    offsets: Arc<Mutex<Option<Arc<TrackerConfig>>>>,
     sh_pm_rx: mpsc::Receiver<SignalPacket>,
     collector_rx: mpsc::Receiver<CollectorMessage>,
     // (url, attempt_count) — 외부 연결 자동 재연결 채널
     reconnect_tx: mpsc::Sender<(String, u32)>,
     reconnect_rx: mpsc::Receiver<(String, u32)>,
+    sample_rate_tx: watch::Sender<SampleRateControl>,
 }
 
 impl RtcSupervisor {
-    pub fn new(offsets: Arc<Mutex<Option<TrackerConfig>>>) -> Self {
+    pub fn new(offsets: Arc<Mutex<Option<Arc<TrackerConfig>>>>) -> Self {
         let (sh_pm_tx, sh_pm_rx) = mpsc::channel(128);
         let (pm_sh_tx, pm_sh_rx) = mpsc::channel(128);
         let (_collector_tx, collector_rx) = mpsc::channel(128);
         let (reconnect_tx, reconnect_rx) = mpsc::channel(4);
+        let (sample_rate_tx, _) = watch::channel(SampleRateControl::default());
 
         let signaling_handler = SignalingHandler::new(sh_pm_tx, pm_sh_rx);
         let peer_manager = PeerManager::new(pm_sh_tx);
@@ -50,6 +54,7 @@ impl RtcSupervisor {
             collector_rx,
             reconnect_tx,
             reconnect_rx,
+            sample_rate_tx,
         }
     }
 
@@ -63,6 +68,12 @@ impl RtcSupervisor {
     ) -> Result<(), String> {
         log::info!("Starting RtcSupervisor...");
 
+        let max_sample_rate = util::get_config(app_handle.clone())
+            .await
+            .ok()
+            .and_then(|c| c.max_sample_rate);
+        self.sample_rate_tx.send_modify(|c| c.set_max_hz(max_sample_rate));
+
         if let Err(e) = self
             .signaling_handler
             .restart_local_server(app_handle.clone(), ip, port)
@@ -90,17 +101,26 @@ impl RtcSupervisor {
                     let result = match event.msg {
                         RtcSignal::NewPeer => {
                             let result = self.peer_manager.handle_new_external_client(client_id).await;
+                            self.update_peer_sample_rate();
                             self.try_start_collector().await;
                             result
                         }
                         RtcSignal::PeerLeft => {
-                            self.peer_manager.handle_client_disconnect(client_id).await
+                            let result = self.peer_manager.handle_client_disconnect(client_id).await;
+                            self.update_peer_sample_rate();
+                            result
                         }
                         RtcSignal::NewLocalPeer => {
                             let result = self.peer_manager.handle_new_local_client(client_id).await;
+                            self.update_peer_sample_rate();
                             self.try_start_collector().await;
                             result
                         }
+                        RtcSignal::RequestSampleRate(hz) => {
+                            self.peer_manager.set_requested_sample_rate(client_id, hz);
+                            self.update_peer_sample_rate();
+                            Ok(())
+                        }
                         _ => self.peer_manager.handle_signaling_message(event).await,
                     };
 
@@ -122,7 +142,11 @@ impl RtcSupervisor {
                         CollectorMessage::Terminated => {
                             log::error!("Process terminated. Detaching...");
                             self.detach_process().await;
-                            util::mutate_global_state(&app_handle, |s| s.proc_state = 0);
+                            util::mutate_global_state(&app_handle, |s| {
+                                s.proc_state = 0;
+                                s.map_name = None;
+                                s.sample_rate = None;
+                            });
                         }
                         CollectorMessage::TemporalError(e) => {
                             if let Err(e) = app_handle.emit("handle-tracker-error", e.clone()) {
@@ -133,6 +157,24 @@ impl RtcSupervisor {
                             log::info!("Successfully found and locked onto offset: {}", name);
                             util::mutate_global_state(&app_handle, |s| s.active_offset_name = Some(name.clone()));
                         }
+                        CollectorMessage::MapChanged(map_name) => {
+                            if let Err(e) = app_handle.emit("handle-map-change", map_name.clone()) {
+                                log::error!("Error sending map change to frontend: {}", e);
+                            }
+                            self.peer_manager.set_map_name(map_name.clone());
+                            util::mutate_global_state(&app_handle, |s| s.map_name = Some(map_name));
+                        }
+                        CollectorMessage::SampleRateChanged(hz) => {
+                            log::debug!("Sample rate changed: {} Hz", hz);
+                            util::mutate_global_state(&app_handle, |s| s.sample_rate = Some(hz));
+                        }
+                        CollectorMessage::ConfigVerified(last_updated) => {
+                            app_handle.state::<TauriState>().offset_sources.report_verified(last_updated).await;
+                        }
+                        CollectorMessage::ConfigExhausted(last_updated) => {
+                            log::warn!("오프셋 설정 {}으로 플레이어를 찾지 못했습니다.", last_updated);
+                            app_handle.state::<TauriState>().offset_sources.report_exhausted(last_updated).await;
+                        }
                     }
                 }
 
@@ -203,6 +245,16 @@ impl RtcSupervisor {
                                 }
                             }
                         }
+                        SupervisorCommand::RecordSnapshot(responder) => {
+                            let result = self.record_snapshot(app_handle.clone()).await;
+                            let _ = responder.send(result);
+                        }
+                        SupervisorCommand::DiagnoseChains(responder) => {
+                            let _ = responder.send(self.diagnose_chains().await);
+                        }
+                        SupervisorCommand::SetMaxSampleRate(max_hz) => {
+                            self.sample_rate_tx.send_modify(|c| c.set_max_hz(max_hz));
+                        }
                     }
                 }
             }
@@ -224,18 +276,17 @@ impl RtcSupervisor {
                 .app_config_dir()
                 .map_err(|e| e.to_string())?;
 
-            let scan_config = self.offsets.lock().await
-                .as_ref()
-                .map(|c| c.gworld_scan.clone());
+            let (scan_config, variants) = self.scan_inputs().await;
 
+            let proc_cache_dir = cache_dir.clone();
             let win_proc = tokio::task::spawn_blocking(move || {
-                crate::game_launcher::launch_and_create_proc(&path_str, cache_dir, scan_config)
+                crate::game_launcher::launch_and_create_proc(&path_str, proc_cache_dir, scan_config, variants)
             })
             .await
             .map_err(|e| format!("태스크 실패: {}", e))?
             .map_err(|e| e.to_string())?;
 
-            let collector = NativeCollector::from_win_proc(win_proc);
+            let collector = NativeCollector::from_win_proc(win_proc, &cache_dir);
             *self.collector_state.instance.lock().await = Some(collector);
             log::info!("Game launched and attached via process handle.");
             self.try_start_collector().await;
@@ -247,6 +298,12 @@ impl RtcSupervisor {
         {
             crate::game_launcher::launch_game(path).map_err(|e| e.to_string())
         }
+
+        #[cfg(target_os = "linux")]
+        {
+            let _ = (app_handle, path);
+            Err("Linux에서는 게임 직접 실행을 지원하지 않습니다. Steam(Proton)으로 게임을 실행한 뒤 연결해 주세요.".to_string())
+        }
     }
 
     pub async fn attach_process(&mut self, app_handle: AppHandle, proc_name: &str) -> Result<(), String> {
@@ -260,11 +317,9 @@ impl RtcSupervisor {
             .app_config_dir()
             .map_err(|e| e.to_string())?;
 
-        let scan_config = self.offsets.lock().await
-            .as_ref()
-            .map(|c| c.gworld_scan.clone());
+        let (scan_config, variants) = self.scan_inputs().await;
 
-        match NativeCollector::new(proc_name, cache_dir, scan_config).await {
+        match NativeCollector::new(proc_name, cache_dir, scan_config, variants).await {
             Ok(collector) => {
                 *self.collector_state.instance.lock().await = Some(collector);
                 log::info!("Process attached successfully.");
@@ -276,12 +331,78 @@ impl RtcSupervisor {
         }
     }
 
+    /// GWorld 스캔 설정과, 스캔 후보 검증에 쓸 오프셋 variant 목록
+    async fn scan_inputs(&self) -> (Option<GWorldScanConfig>, Vec<WuwaOffset>) {
+        let offsets = self.offsets.lock().await;
+        match offsets.as_ref() {
+            Some(c) => (Some(c.gworld_scan.clone()), c.offsets.clone()),
+            None => (None, Vec::new()),
+        }
+    }
+
+    pub async fn record_snapshot(&mut self, app_handle: AppHandle) -> Result<String, String> {
+        let variants = self.offsets.lock().await
+            .as_ref()
+            .map(|c| c.offsets.clone())
+            .unwrap_or_default();
+
+        let snapshot = {
+            let collector_guard = self.collector_state.instance.lock().await;
+            let Some(collector) = collector_guard.as_ref() else {
+                return Err("게임에 연결되어 있지 않습니다.".to_string());
+            };
+            collector.record_snapshot(&variants)
+        };
+
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let path = app_handle
+            .path()
+            .app_config_dir()
+            .map_err(|e| e.to_string())?
+            .join("snapshots")
+            .join(format!("snapshot-{}.json", secs));
+        snapshot.save(&path).map_err(|e| e.to_string())?;
+        log::info!("메모리 스냅샷 저장: {} (영역 {}개)", path.display(), snapshot.regions.len());
+        Ok(path.to_string_lossy().into_owned())
+    }
+
+    pub async fn diagnose_chains(&mut self) -> Result<String, String> {
+        let variants = self.offsets.lock().await
+            .as_ref()
+            .map(|c| c.offsets.clone())
+            .ok_or_else(|| "오프셋 데이터를 불러오는 중입니다...".to_string())?;
+
+        let report = {
+            let collector_guard = self.collector_state.instance.lock().await;
+            let Some(collector) = collector_guard.as_ref() else {
+                return Err("게임에 연결되어 있지 않습니다.".to_string());
+            };
+            collector.diagnose_chains(&variants)
+        };
+
+        let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+        log::info!("포인터 체인 진단 리포트:\n{}", json);
+        Ok(json)
+    }
+
     pub async fn detach_process(&mut self) {
         log::info!("Detaching from process by external signal.");
         self.try_stop_collector().await;
         *self.collector_state.instance.lock().await = None;
     }
 
+    fn update_peer_sample_rate(&mut self) {
+        let peer_count = self.peer_manager.peer_count();
+        let requested_hz = self.peer_manager.requested_sample_rate();
+        self.sample_rate_tx.send_modify(|c| {
+            c.peer_count = peer_count;
+            c.requested_hz = requested_hz;
+        });
+    }
+
     async fn try_start_collector(&mut self) {
         if self.collector_state.instance.lock().await.is_some()
             && self.collector_state.cancel.is_none()
@@ -298,6 +419,7 @@ impl RtcSupervisor {
                 pm_tx,
                 cancel,
                 self.offsets.clone(),
+                self.sample_rate_tx.subscribe(),
             ));
         }
     }
@@ -0,0 +1,143 @@
+use anyhow::{Result, bail};
+use memchr::memchr_iter;
+
+/// 마스크 바이트 패턴. "48 8B 1D ?? ?? ?? ?? 48 85 DB" 형식이며 ?? / ? 는 wildcard.
+/// 프로세스 메모리, 파일 등 바이트 슬라이스라면 어디서든 검색할 수 있다.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pattern {
+    bytes: Vec<Option<u8>>,
+    // 검색 앵커: memchr로 찾을 고정 바이트의 위치와 값
+    anchor: Option<(usize, u8)>,
+}
+
+impl Pattern {
+    pub fn parse(s: &str) -> Result<Self> {
+        let bytes = s
+            .split_whitespace()
+            .map(|token| match token {
+                "?" | "??" => Ok(None),
+                // from_str_radix는 "+8" 같은 부호도 받으므로 16진 숫자 두 개인지 직접 확인한다.
+                _ if token.len() == 2 && token.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                    Ok(u8::from_str_radix(token, 16).ok())
+                }
+                _ => bail!("잘못된 패턴 바이트: '{}'", token),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if bytes.is_empty() {
+            bail!("빈 패턴입니다.");
+        }
+        let anchor = Self::pick_anchor(&bytes);
+        Ok(Self { bytes, anchor })
+    }
+
+    /// 흔한 바이트(0x00, 0xFF, 0xCC, 0x90, REX.W 0x48)는 후보가 너무 많으므로 가능하면 피한다.
+    fn pick_anchor(bytes: &[Option<u8>]) -> Option<(usize, u8)> {
+        const COMMON: &[u8] = &[0x00, 0xFF, 0xCC, 0x90, 0x48];
+        let fixed = || bytes.iter().enumerate().filter_map(|(i, b)| b.map(|b| (i, b)));
+        fixed()
+            .find(|(_, b)| !COMMON.contains(b))
+            .or_else(|| fixed().next())
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// parse가 빈 패턴을 거부하므로 항상 false다.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn matches_at(&self, haystack: &[u8], offset: usize) -> bool {
+        haystack.len().saturating_sub(offset) >= self.bytes.len()
+            && self
+                .bytes
+                .iter()
+                .zip(&haystack[offset..])
+                .all(|(p, b)| p.is_none_or(|p| p == *b))
+    }
+
+    /// 일치하는 모든 시작 위치를 오름차순으로 반환한다.
+    pub fn find_iter<'a>(&'a self, haystack: &'a [u8]) -> Box<dyn Iterator<Item = usize> + 'a> {
+        if haystack.len() < self.bytes.len() {
+            return Box::new(std::iter::empty());
+        }
+        let last_start = haystack.len() - self.bytes.len();
+        match self.anchor {
+            Some((pos, byte)) => Box::new(
+                memchr_iter(byte, &haystack[pos..=last_start + pos])
+                    .filter(move |&start| self.matches_at(haystack, start)),
+            ),
+            // 전부 wildcard인 패턴은 모든 위치가 일치한다.
+            None => Box::new(0..=last_start),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find_all(pattern: &str, haystack: &[u8]) -> Vec<usize> {
+        Pattern::parse(pattern).unwrap().find_iter(haystack).collect()
+    }
+
+    #[test]
+    fn rejects_malformed_patterns() {
+        assert!(Pattern::parse("").is_err());
+        assert!(Pattern::parse("   ").is_err());
+        assert!(Pattern::parse("48 8").is_err());
+        assert!(Pattern::parse("48 8B1D").is_err());
+        assert!(Pattern::parse("48 GG").is_err());
+        assert!(Pattern::parse("48 ???").is_err());
+        assert!(Pattern::parse("48 +8").is_err());
+
+        let pattern = Pattern::parse("48 8b ? ??").unwrap();
+        assert_eq!(pattern.len(), 4);
+        assert!(!pattern.is_empty());
+    }
+
+    #[test]
+    fn wildcards_match_any_byte() {
+        let haystack = [0x48, 0x00, 0x1D, 0x48, 0xFF, 0x1D, 0x48, 0xFF, 0x1E];
+        assert_eq!(find_all("48 ?? 1D", &haystack), [0, 3]);
+        assert_eq!(find_all("48 ? 1D", &haystack), [0, 3]);
+        assert_eq!(find_all("48 FF ??", &haystack), [3, 6]);
+    }
+
+    #[test]
+    fn returns_every_hit_in_ascending_order() {
+        let haystack = [0x8B, 0x1D, 0x8B, 0x1D, 0x8B, 0x00, 0x8B, 0x1D];
+        assert_eq!(find_all("8B 1D", &haystack), [0, 2, 6]);
+        // 겹치는 일치도 모두 반환한다.
+        assert_eq!(find_all("8B ?? 8B", &haystack), [0, 2, 4]);
+    }
+
+    #[test]
+    fn anchor_skips_common_bytes() {
+        assert_eq!(Pattern::parse("48 00 ?? 8B 1D").unwrap().anchor, Some((3, 0x8B)));
+        // 흔한 바이트뿐이면 첫 고정 바이트를 쓴다.
+        assert_eq!(Pattern::parse("?? 48 00 FF").unwrap().anchor, Some((1, 0x48)));
+
+        // 앵커가 패턴 중간에 있어도 시작 위치를 반환한다.
+        let haystack = [0x48, 0x00, 0x12, 0x8B, 0x1D, 0x48, 0x00];
+        assert_eq!(find_all("48 00 ?? 8B 1D", &haystack), [0]);
+    }
+
+    #[test]
+    fn all_wildcard_pattern_matches_everywhere() {
+        let pattern = Pattern::parse("?? ?").unwrap();
+        assert_eq!(pattern.anchor, None);
+        assert_eq!(pattern.find_iter(&[1, 2, 3, 4]).collect::<Vec<_>>(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn haystack_shorter_than_pattern() {
+        let pattern = Pattern::parse("48 8B 1D").unwrap();
+        assert_eq!(pattern.find_iter(&[0x48, 0x8B]).count(), 0);
+        assert_eq!(pattern.find_iter(&[]).count(), 0);
+        assert!(!pattern.matches_at(&[0x48, 0x8B, 0x1D], 1));
+        assert!(!pattern.matches_at(&[0x48, 0x8B, 0x1D], 4));
+        assert_eq!(find_all("?? ??", &[0x00]), Vec::<usize>::new());
+    }
+}
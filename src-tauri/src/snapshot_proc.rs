@@ -0,0 +1,357 @@
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+
+use crate::analysis_cache::BuildKey;
+use crate::game_version::GameBuild;
+use crate::offsets::WuwaOffset;
+use crate::process_backend::{ProcessBackend, classify_ptr};
+use crate::types::{MemoryRegion, NativeError, PageState, Protection, ReadFailure, ReadFault};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// 실제 게임 없이 포인터 체인 로직을 재현하기 위한 메모리 스냅샷.
+/// (주소, 바이트) 영역 목록 + 모듈 base + GWorld RVA로 구성된다.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MemorySnapshot {
+    pub module_base: u64,
+    /// None이면 오프셋의 global_gworld 폴백을 사용한다. (WinProc의 "fb"와 동일)
+    #[serde(default)]
+    pub gworld_rva: Option<u64>,
+    pub regions: Vec<SnapshotRegion>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SnapshotRegion {
+    pub address: u64,
+    #[serde(with = "hex_bytes")]
+    pub bytes: Vec<u8>,
+}
+
+impl MemorySnapshot {
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = fs::read_to_string(path).context("스냅샷 파일을 읽지 못했습니다.")?;
+        serde_json::from_str(&data).context("스냅샷 파일 파싱 실패")
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json).context("스냅샷 저장 실패")
+    }
+}
+
+// ── SnapshotProc ──────────────────────────────────────────────────────────────
+
+/// 기록된 스냅샷을 읽기 전용 프로세스처럼 제공하는 결정적 백엔드.
+pub struct SnapshotProc {
+    snapshot: MemorySnapshot,
+}
+
+impl SnapshotProc {
+    pub fn new(snapshot: MemorySnapshot) -> Self {
+        Self { snapshot }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        Ok(Self::new(MemorySnapshot::load(path)?))
+    }
+}
+
+impl ProcessBackend for SnapshotProc {
+    fn is_alive(&self) -> bool {
+        true
+    }
+
+    fn read_bytes(&self, address: u64, buffer: &mut [u8]) -> Result<(), NativeError> {
+        let len = buffer.len() as u64;
+        // 주소 공간 끝을 넘는 읽기는 기록될 수 없으므로 기록되지 않은 영역으로 본다.
+        let region = address.checked_add(len).and_then(|end| {
+            self.snapshot.regions.iter().find(|r| {
+                address >= r.address
+                    && r.address.checked_add(r.bytes.len() as u64).is_some_and(|r_end| end <= r_end)
+            })
+        });
+        match region {
+            Some(r) => {
+                let start = (address - r.address) as usize;
+                buffer.copy_from_slice(&r.bytes[start..start + buffer.len()]);
+                Ok(())
+            }
+            None => Err(NativeError::value_read(
+                ReadFailure::at(address)
+                    .fault(ReadFault::Unmapped, None)
+                    .note(format!("스냅샷에 기록되지 않은 영역 (+{:X})", len)),
+            )),
+        }
+    }
+
+    /// 기록된 영역을 읽기 전용 영역으로 보고한다. (겹치는 기록은 합치지 않음)
+    fn regions(&self) -> Vec<MemoryRegion> {
+        let mut regions: Vec<MemoryRegion> = self
+            .snapshot
+            .regions
+            .iter()
+            .map(|r| MemoryRegion {
+                base: r.address,
+                size: r.bytes.len() as u64,
+                state: PageState::Commit,
+                protection: Protection { read: true, ..Default::default() },
+                module: None,
+            })
+            .collect();
+        regions.sort_by_key(|r| r.base);
+        regions
+    }
+
+    fn gworld_ready(&self) -> bool {
+        self.snapshot.gworld_rva.is_some()
+    }
+
+    fn module_base(&self) -> u64 {
+        self.snapshot.module_base
+    }
+
+    fn gworld_rva(&self) -> Option<u64> {
+        self.snapshot.gworld_rva
+    }
+
+    fn active_offset_name(&self, offset: &WuwaOffset) -> String {
+        match self.snapshot.gworld_rva {
+            Some(rva) => format!("{:X}", rva),
+            None => format!("오프셋 {}", offset.name),
+        }
+    }
+
+    fn read_gworld(&self, offset: &WuwaOffset) -> Result<u64, NativeError> {
+        read_gworld_at(self, offset)
+    }
+}
+
+// ── MemoryRecorder ────────────────────────────────────────────────────────────
+
+/// 실제 백엔드를 감싸 read_player_info가 읽은 모든 영역을 기록한다.
+pub struct MemoryRecorder<'a, B: ProcessBackend> {
+    inner: &'a B,
+    regions: RefCell<Vec<SnapshotRegion>>,
+}
+
+impl<'a, B: ProcessBackend> MemoryRecorder<'a, B> {
+    pub fn new(inner: &'a B) -> Self {
+        Self { inner, regions: RefCell::new(Vec::new()) }
+    }
+
+    pub fn into_snapshot(self) -> MemorySnapshot {
+        MemorySnapshot {
+            module_base: self.inner.module_base(),
+            gworld_rva: self.inner.gworld_rva(),
+            regions: self.regions.into_inner(),
+        }
+    }
+}
+
+impl<B: ProcessBackend> ProcessBackend for MemoryRecorder<'_, B> {
+    fn is_alive(&self) -> bool {
+        self.inner.is_alive()
+    }
+
+    fn read_bytes(&self, address: u64, buffer: &mut [u8]) -> Result<(), NativeError> {
+        self.inner.read_bytes(address, buffer)?;
+        let mut regions = self.regions.borrow_mut();
+        let recorded = regions
+            .iter()
+            .any(|r| r.address == address && r.bytes.len() == buffer.len());
+        if !recorded {
+            regions.push(SnapshotRegion { address, bytes: buffer.to_vec() });
+        }
+        Ok(())
+    }
+
+    fn regions(&self) -> Vec<MemoryRegion> {
+        self.inner.regions()
+    }
+
+    fn query_region(&self, address: u64) -> Option<MemoryRegion> {
+        self.inner.query_region(address)
+    }
+
+    fn gworld_ready(&self) -> bool {
+        self.inner.gworld_ready()
+    }
+
+    fn module_base(&self) -> u64 {
+        self.inner.module_base()
+    }
+
+    fn gworld_rva(&self) -> Option<u64> {
+        self.inner.gworld_rva()
+    }
+
+    fn active_offset_name(&self, offset: &WuwaOffset) -> String {
+        self.inner.active_offset_name(offset)
+    }
+
+    fn game_build(&self) -> Option<&GameBuild> {
+        self.inner.game_build()
+    }
+
+    fn build_key(&self) -> Option<BuildKey> {
+        self.inner.build_key()
+    }
+
+    // 내부 백엔드의 read_gworld는 기록을 거치지 않으므로 같은 주소를 직접 읽는다.
+    fn read_gworld(&self, offset: &WuwaOffset) -> Result<u64, NativeError> {
+        read_gworld_at(self, offset)
+    }
+}
+
+fn read_gworld_at<B: ProcessBackend>(backend: &B, offset: &WuwaOffset) -> Result<u64, NativeError> {
+    let (rva, source) = match backend.gworld_rva() {
+        Some(rva) => (rva, "scan"),
+        None => (offset.global_gworld, "fb"),
+    };
+    let target = backend.module_base() + rva;
+    backend.read_memory::<u64>(target).map_err(|e| {
+        let base = backend.module_base();
+        e.in_chain(&format!("gworld[{}]", source), base, rva, classify_ptr(base))
+    })
+}
+
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        serializer.serialize_str(&hex)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        if hex.len() % 2 != 0 || !hex.is_ascii() {
+            return Err(D::Error::custom("잘못된 hex 문자열입니다."));
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(D::Error::custom))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::process_backend::select_player_info;
+    use crate::types::PtrClass;
+    use crate::variant_stats::VariantStats;
+
+    /// 레거시 체인 하나가 기록된 스냅샷.
+    /// UWorld+0x1B8이 실제 OwningGameInstance이고, +0x180은 NULL, +0x1C0/+0x1C8은 비정상/비정렬 포인터다.
+    /// 플레이어 위치는 (1000.5, -2000.25, 300.0) + 월드 원점 (100, 200, -300), yaw 90°.
+    pub(crate) fn fixture_snapshot() -> MemorySnapshot {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/snapshot_legacy_chain.json");
+        MemorySnapshot::load(&path).unwrap()
+    }
+
+    fn fixture() -> SnapshotProc {
+        SnapshotProc::new(fixture_snapshot())
+    }
+
+    pub(crate) fn variant(name: &str, owning_game_instance: u64) -> WuwaOffset {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "global_gworld": 0,
+            "uworld_persistentlevel": 0x30,
+            "uworld_owninggameinstance": owning_game_instance,
+            "ulevel_lastworldorigin": 0xB8,
+            "ugameinstance_localplayers": 0x38,
+            "uplayer_playercontroller": 0x30,
+            "aplayercontroller_acknowlegedpawn": 0x2A0,
+            "aactor_rootcomponent": 0x130,
+            "uscenecomponent_componenttoworld": 0x1C0,
+        }))
+        .unwrap()
+    }
+
+    fn chain_failure(proc: &SnapshotProc, offset: WuwaOffset) -> ReadFailure {
+        let err = select_player_info(proc, &mut None, &[offset], None).err().unwrap();
+        err.failure().cloned().unwrap()
+    }
+
+    #[test]
+    fn selects_working_variant() {
+        let proc = fixture();
+        let mut cached = None;
+        let (info, resolved) = select_player_info(&proc, &mut cached, &[variant("current", 0x1B8)], None).unwrap();
+
+        assert_eq!(cached.map(|o| o.name).as_deref(), Some("current"));
+        assert_eq!((info.x, info.y, info.z), (1100.5, -1800.25, 0.0));
+        assert!((info.yaw - 90.0).abs() < 1e-3);
+        assert_eq!(resolved.transform_addr, 0x1_0000_81C0);
+    }
+
+    #[test]
+    fn falls_back_to_next_variant() {
+        let proc = fixture();
+        let offsets = [variant("previous", 0x180), variant("current", 0x1B8)];
+        let mut cached = None;
+        let mut stats = VariantStats::in_memory();
+        select_player_info(&proc, &mut cached, &offsets, Some(&mut stats)).unwrap();
+
+        assert_eq!(cached.map(|o| o.name).as_deref(), Some("current"));
+        // 다음 선택에서는 성공한 variant가 먼저 시도된다.
+        assert!(stats.rank("current") < stats.rank("previous"));
+    }
+
+    #[test]
+    fn reports_first_variant_failure_when_all_fail() {
+        let proc = fixture();
+        let offsets = [variant("previous", 0x180), variant("garbage", 0x1C0)];
+        let failure = select_player_info(&proc, &mut None, &offsets, None).err().unwrap().failure().cloned().unwrap();
+
+        assert_eq!(failure.stage.as_deref(), Some("TArray<*LocalPlayers>"));
+        assert_eq!(failure.pointer, Some(PtrClass::Null));
+    }
+
+    #[test]
+    fn classifies_broken_pointers() {
+        let proc = fixture();
+        assert_eq!(chain_failure(&proc, variant("null", 0x180)).pointer, Some(PtrClass::Null));
+        assert_eq!(chain_failure(&proc, variant("non-canonical", 0x1C0)).pointer, Some(PtrClass::NonCanonical));
+        assert_eq!(chain_failure(&proc, variant("misaligned", 0x1C8)).pointer, Some(PtrClass::Misaligned));
+
+        assert_eq!(classify_ptr(0x20), PtrClass::NearNull);
+        assert_eq!(classify_ptr(proc.module_base()), PtrClass::Ok);
+    }
+
+    #[test]
+    fn only_non_loading_failures_implicate_config() {
+        let proc = fixture();
+        let failure = |name, slot| select_player_info(&proc, &mut None, &[variant(name, slot)], None).err().unwrap();
+        // NULL 체인은 타이틀/로딩 화면에서도 나므로 롤백 근거가 아니다.
+        assert!(failure("null", 0x180).is_loading_state());
+        assert!(!failure("non-canonical", 0x1C0).is_loading_state());
+        assert!(!failure("misaligned", 0x1C8).is_loading_state());
+    }
+
+    #[test]
+    fn recorded_snapshot_replays_the_same_reads() {
+        let proc = fixture();
+        let offsets = [variant("current", 0x1B8)];
+        let recorder = MemoryRecorder::new(&proc);
+        let (recorded, _) = select_player_info(&recorder, &mut None, &offsets, None).unwrap();
+
+        let replay = SnapshotProc::new(recorder.into_snapshot());
+        let (replayed, _) = select_player_info(&replay, &mut None, &offsets, None).unwrap();
+        assert_eq!((recorded.x, recorded.y, recorded.z), (replayed.x, replayed.y, replayed.z));
+    }
+
+    #[test]
+    fn reads_past_the_address_space_end_are_unmapped() {
+        let proc = fixture();
+        let mut buffer = [0u8; 8];
+        let err = proc.read_bytes(u64::MAX - 3, &mut buffer).unwrap_err();
+        assert_eq!(err.failure().and_then(|f| f.fault), Some(ReadFault::Unmapped));
+    }
+}
@@ -15,15 +15,14 @@ use webrtc::peer_connection::sdp::session_description::RTCSessionDescription;
 #[repr(C)]
 #[derive(Copy, Clone, serde::Serialize, serde::Deserialize, Debug)]
 pub struct PlayerInfo {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
     pub pitch: f32,
     pub yaw: f32,
     pub roll: f32,
 }
 
-#[repr(C)]
 #[derive(Copy, Clone, serde::Serialize)]
 pub struct FIntVector {
     pub x: i32,
@@ -31,19 +30,13 @@ pub struct FIntVector {
     pub z: i32,
 }
 
-#[repr(C)]
-#[derive(Copy, Clone, serde::Serialize)]
-pub struct FTransformDouble {
-    pub rot_x: f32,
-    pub rot_y: f32,
-    pub rot_z: f32,
-    pub rot_w: f32,
-    pub loc_x: f32,
-    pub loc_y: f32,
-    pub loc_z: f32,
-    pub scale_x: f32,
-    pub scale_y: f32,
-    pub scale_z: f32,
+/// TransformLayout에 따라 읽은 FTransform. 원격 저장 형식(f32/f64)과 관계없이 f64로 담는다.
+#[derive(Copy, Clone, Debug, Default, serde::Serialize)]
+pub struct FTransform {
+    /// 쿼터니언 (x, y, z, w)
+    pub rotation: [f64; 4],
+    pub translation: [f64; 3],
+    pub scale: [f64; 3],
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
@@ -55,6 +48,14 @@ pub struct LocalStorageConfig {
     pub auto_attach_enabled: Option<bool>,
     pub start_in_tray: Option<bool>,
     pub game_path: Option<String>,
+    /// 움직일 때의 최대 샘플링 Hz (기본 30, 최대 60)
+    pub max_sample_rate: Option<u32>,
+    /// 오프셋을 가져올 소스 (https URL 또는 file:// 경로, 앞에서부터 시도). 없으면 기본 소스
+    #[serde(default)]
+    pub offset_sources: Option<Vec<String>>,
+    /// 켜면 원격 소스를 사용하지 않고 로컬 캐시만 사용한다.
+    #[serde(default)]
+    pub offline_mode: Option<bool>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
@@ -69,6 +70,9 @@ pub enum RtcSignal {
     NewLocalPeer,
     LocalOffer,
     Data(PlayerInfo),
+    MapChanged(String),
+    /// 클라이언트가 원하는 샘플링 Hz
+    RequestSampleRate(u32),
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
@@ -83,41 +87,370 @@ pub enum NativeError {
     #[error("proc_terminated")]
     ProcessTerminated,
 
-    #[error("{message}")]
-    PointerChainError { message: String },
+    #[error("offsets_not_loaded")]
+    OffsetsNotLoaded,
+
+    /// 포인터 체인의 한 단계(GWorld 포함)를 따라가지 못함
+    #[error("{0}")]
+    PointerChainError(Box<ReadFailure>),
+
+    /// 최종 값을 읽지 못했거나, 읽은 값이 비정상
+    #[error("{0}")]
+    ValueReadError(Box<ReadFailure>),
+
+    #[error("unsupported game version: {build}")]
+    UnsupportedGameVersion { build: String },
+}
+
+/// 메모리 읽기 실패 상세. 백엔드의 read_bytes가 주소/OS 에러를 채우고,
+/// 체인 해석 쪽에서 단계 이름과 base+offset을 덧붙인다.
+#[derive(Clone, Debug, Default, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadFailure {
+    /// 실패한 단계 (체인 Deref 이름, "gworld", "ftrans" 등)
+    pub stage: Option<String>,
+    #[serde(serialize_with = "hex_addr::serialize")]
+    pub address: u64,
+    /// 체인 단계에서 실패한 경우: 역참조하려던 포인터와 필드 오프셋
+    #[serde(serialize_with = "hex_addr::serialize_opt")]
+    pub base: Option<u64>,
+    #[serde(serialize_with = "hex_addr::serialize_opt")]
+    pub field_offset: Option<u64>,
+    pub pointer: Option<PtrClass>,
+    pub fault: Option<ReadFault>,
+    /// Windows: GetLastError, Linux: errno
+    pub os_code: Option<i32>,
+    pub page: Option<PageInfo>,
+    /// 비정상 값 등 추가 설명
+    pub note: Option<String>,
+}
+
+/// 읽기 실패의 OS 수준 원인
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReadFault {
+    /// 일부만 읽힘 (ERROR_PARTIAL_COPY / EFAULT / 짧은 읽기)
+    Partial,
+    /// ERROR_ACCESS_DENIED / EPERM
+    AccessDenied,
+    /// ERROR_INVALID_HANDLE / ESRCH
+    InvalidHandle,
+    /// 스냅샷/파일 이미지에 없는 주소
+    Unmapped,
+    Other,
+}
+
+/// 포인터 값의 타당성 분류 (process_backend::classify_ptr)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PtrClass {
+    Null,
+    NearNull,
+    NonCanonical,
+    Misaligned,
+    Ok,
+}
+
+/// 실패한 주소가 속한 영역의 요약 (MemoryRegion에서 만든다)
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    pub state: PageState,
+    pub guard: bool,
+    pub no_access: bool,
+    #[serde(serialize_with = "hex_addr::serialize")]
+    pub region_base: u64,
+    #[serde(serialize_with = "hex_addr::serialize")]
+    pub region_size: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PageState {
+    Commit,
+    Reserve,
+    Free,
+    Unknown,
+}
+
+/// 프로세스 가상 메모리의 한 영역 (VirtualQueryEx / mach_vm_region / /proc/<pid>/maps)
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryRegion {
+    #[serde(serialize_with = "hex_addr::serialize")]
+    pub base: u64,
+    #[serde(serialize_with = "hex_addr::serialize")]
+    pub size: u64,
+    pub state: PageState,
+    pub protection: Protection,
+    /// 매핑된 모듈/파일 경로. 익명 메모리는 None
+    pub module: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize)]
+pub struct Protection {
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+    /// Windows PAGE_GUARD: 첫 접근 시 예외가 나므로 읽을 수 없는 것으로 본다.
+    pub guard: bool,
+}
+
+impl MemoryRegion {
+    pub fn end(&self) -> u64 {
+        self.base.saturating_add(self.size)
+    }
+
+    pub fn contains(&self, address: u64) -> bool {
+        (self.base..self.end()).contains(&address)
+    }
+
+    /// 커밋되어 있고 지금 읽을 수 있는 영역인지
+    pub fn is_readable(&self) -> bool {
+        self.state == PageState::Commit && self.protection.read && !self.protection.guard
+    }
+
+    pub fn page_info(&self) -> PageInfo {
+        PageInfo {
+            state: self.state,
+            guard: self.protection.guard,
+            no_access: self.state == PageState::Commit && !self.protection.read,
+            region_base: self.base,
+            region_size: self.size,
+        }
+    }
+}
+
+impl std::fmt::Display for Protection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let flag = |on: bool, c: char| if on { c } else { '-' };
+        write!(f, "{}{}{}", flag(self.read, 'r'), flag(self.write, 'w'), flag(self.execute, 'x'))?;
+        if self.guard {
+            write!(f, "+GUARD")?;
+        }
+        Ok(())
+    }
+}
+
+impl ReadFailure {
+    pub fn at(address: u64) -> Self {
+        Self { address, ..Default::default() }
+    }
 
-    #[error("{message}")]
-    ValueReadError { message: String },
+    pub fn stage(mut self, stage: impl Into<String>) -> Self {
+        self.stage = Some(stage.into());
+        self
+    }
+
+    pub fn note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    pub fn pointer(mut self, pointer: PtrClass) -> Self {
+        self.pointer = Some(pointer);
+        self
+    }
+
+    pub fn fault(mut self, fault: ReadFault, os_code: Option<i32>) -> Self {
+        self.fault = Some(fault);
+        self.os_code = os_code;
+        self
+    }
+}
+
+impl std::fmt::Display for ReadFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{:X}", self.stage.as_deref().unwrap_or("read"), self.address)?;
+        if let (Some(base), Some(field_offset)) = (self.base, self.field_offset) {
+            write!(f, " [{:X}+{:X}]", base, field_offset)?;
+        }
+        if let Some(pointer) = self.pointer {
+            write!(f, " {}", pointer)?;
+        }
+        if let Some(code) = self.os_code {
+            write!(f, " e{}", code)?;
+        }
+        if let Some(fault) = self.fault {
+            write!(f, " {:?}", fault)?;
+        }
+        if let Some(page) = &self.page {
+            write!(f, " {}", page)?;
+        }
+        if let Some(note) = &self.note {
+            write!(f, ": {}", note)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for PtrClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PtrClass::Null => "NULL",
+            PtrClass::NearNull => "~NULL",
+            PtrClass::NonCanonical => "!canon",
+            PtrClass::Misaligned => "!align",
+            PtrClass::Ok => "ok",
+        })
+    }
+}
+
+impl std::fmt::Display for PageInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = match self.state {
+            PageState::Commit => "CMT",
+            PageState::Reserve => "RSV",
+            PageState::Free => "FREE",
+            PageState::Unknown => "?",
+        };
+        let guard = if self.guard { "+GUARD" } else { "" };
+        let no_access = if self.no_access { "+NAX" } else { "" };
+        let verdict = match self.state {
+            PageState::Free | PageState::Reserve => "!commit",
+            PageState::Commit if self.guard || self.no_access => "!ACE",
+            PageState::Commit => "!tmp",
+            PageState::Unknown => "",
+        };
+        write!(
+            f,
+            "[{}{}{}{} @{:X}+{:X}]",
+            state, guard, no_access, verdict, self.region_base, self.region_size
+        )
+    }
 }
 
 impl NativeError {
-    /// 프론트엔드 표시용 간결한 한국어 메시지
-    pub fn user_message(&self) -> &'static str {
+    pub fn pointer_chain(failure: ReadFailure) -> Self {
+        NativeError::PointerChainError(Box::new(failure))
+    }
+
+    pub fn value_read(failure: ReadFailure) -> Self {
+        NativeError::ValueReadError(Box::new(failure))
+    }
+
+    pub fn failure(&self) -> Option<&ReadFailure> {
         match self {
-            NativeError::ProcessTerminated => "게임 프로세스가 종료되었습니다.",
-            NativeError::PointerChainError { message }
-            | NativeError::ValueReadError { message } => {
-                if message.contains("[ACCESS]") {
-                    "메모리 접근 거부 (ACE 핸들 권한 박탈)"
-                } else if message.contains("[INV_HDL]") {
-                    "프로세스 핸들이 무효화되었습니다."
-                } else if message.contains("[PARTIAL]") {
-                    "ACE 메모리 보호 중 (게임 초기화/로딩 중...)"
-                } else if message.contains("NULL") || message.contains("addr=0") {
-                    "게임 월드 초기화 중 (잠시 대기...)"
-                } else {
-                    "메모리 읽기 실패"
-                }
+            NativeError::PointerChainError(failure) | NativeError::ValueReadError(failure) => Some(failure),
+            _ => None,
+        }
+    }
+
+    /// 백엔드 읽기 실패를 체인 단계 실패로 바꾼다. OS 에러/페이지 정보는 그대로 둔다.
+    pub fn in_chain(self, stage: &str, base: u64, field_offset: u64, pointer: PtrClass) -> Self {
+        let mut failure = match self {
+            NativeError::PointerChainError(failure) | NativeError::ValueReadError(failure) => failure,
+            other => return other,
+        };
+        failure.stage = Some(stage.to_string());
+        failure.base = Some(base);
+        failure.field_offset = Some(field_offset);
+        failure.pointer = Some(pointer);
+        NativeError::PointerChainError(failure)
+    }
+
+    /// 값 읽기 단계 이름을 붙인다. (이미 단계 이름이 있으면 유지)
+    pub fn in_value(self, stage: &str) -> Self {
+        match self {
+            NativeError::PointerChainError(mut failure) | NativeError::ValueReadError(mut failure) => {
+                failure.stage.get_or_insert_with(|| stage.to_string());
+                NativeError::ValueReadError(failure)
             }
+            other => other,
+        }
+    }
+
+    /// 프론트엔드 번역 키로 쓰는 고정 코드
+    pub fn code(&self) -> &'static str {
+        let failure = match self {
+            NativeError::ProcessTerminated => return "process-terminated",
+            NativeError::OffsetsNotLoaded => return "offsets-loading",
+            NativeError::UnsupportedGameVersion { .. } => return "unsupported-game-version",
+            NativeError::PointerChainError(failure) | NativeError::ValueReadError(failure) => failure,
+        };
+        match failure.fault {
+            Some(ReadFault::AccessDenied) => return "access-denied",
+            Some(ReadFault::InvalidHandle) => return "invalid-handle",
+            _ => {}
+        }
+        // 체인 도중 NULL을 만나면 부분 읽기 에러보다 월드 미진입이 실제 원인이다.
+        if failure.address == 0 || matches!(failure.pointer, Some(PtrClass::Null | PtrClass::NearNull)) {
+            return "world-loading";
+        }
+        if failure.fault == Some(ReadFault::Partial) {
+            return "memory-protected";
+        }
+        "read-failed"
+    }
+
+    /// 월드 진입 전(NULL 체인)이나 로딩 중 ACE 보호처럼 게임 상태 때문일 수 있는 실패인지.
+    /// 이런 실패는 오프셋 설정이 틀렸다는 근거로 쓰지 않는다.
+    pub fn is_loading_state(&self) -> bool {
+        matches!(self.code(), "world-loading" | "memory-protected")
+    }
+
+    /// 프론트엔드 표시용 간결한 한국어 메시지
+    pub fn user_message(&self) -> &'static str {
+        match self.code() {
+            "process-terminated" => "게임 프로세스가 종료되었습니다.",
+            "offsets-loading" => "오프셋 데이터를 불러오는 중입니다...",
+            "unsupported-game-version" => "지원하지 않는 게임 버전입니다. (오프셋 업데이트 대기 중)",
+            "access-denied" => "메모리 접근 거부 (ACE 핸들 권한 박탈)",
+            "invalid-handle" => "프로세스 핸들이 무효화되었습니다.",
+            "world-loading" => "게임 월드 초기화 중 (잠시 대기...)",
+            "memory-protected" => "ACE 메모리 보호 중 (게임 초기화/로딩 중...)",
+            _ => "메모리 읽기 실패",
+        }
+    }
+}
+
+/// 프론트엔드로 보내는 수집 오류. code는 고정 값이라 번역 키로 쓸 수 있다.
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackerError {
+    pub code: &'static str,
+    /// 기본(한국어) 메시지
+    pub message: &'static str,
+    pub detail: Option<ReadFailure>,
+}
+
+impl From<&NativeError> for TrackerError {
+    fn from(e: &NativeError) -> Self {
+        TrackerError {
+            code: e.code(),
+            message: e.user_message(),
+            detail: e.failure().cloned(),
+        }
+    }
+}
+
+/// JS number는 53비트까지만 정확하므로 주소는 16진수 문자열로 보낸다.
+pub mod hex_addr {
+    use serde::Serializer;
+
+    pub fn serialize<S: Serializer>(value: &u64, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&format!("0x{:X}", value))
+    }
+
+    pub fn serialize_opt<S: Serializer>(value: &Option<u64>, s: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(v) => serialize(v, s),
+            None => s.serialize_none(),
         }
     }
 }
 
 pub enum CollectorMessage {
     Data(PlayerInfo),
-    TemporalError(String),
+    TemporalError(TrackerError),
     Terminated,
     OffsetFound(String),
+    MapChanged(String),
+    SampleRateChanged(u32),
+    /// 이 last_updated의 설정으로 플레이어를 찾았다.
+    ConfigVerified(String),
+    /// 이 last_updated의 설정은 모든 variant와 재스캔이 실패했다.
+    ConfigExhausted(String),
 }
 
 #[derive(Debug)]
@@ -126,6 +459,10 @@ pub enum SupervisorCommand {
     LaunchAndAttach(String, oneshot::Sender<Result<(), String>>),
     RestartSignalingServer,
     RestartExternalConnection(oneshot::Sender<Result<String, String>>),
+    RecordSnapshot(oneshot::Sender<Result<String, String>>),
+    /// 포인터 체인 진단 리포트 (JSON)
+    DiagnoseChains(oneshot::Sender<Result<String, String>>),
+    SetMaxSampleRate(Option<u32>),
 }
 
 // 한 명의 클라이언트에 대한 모든 WebRTC 관련 리소스를 묶는 구조체
@@ -162,6 +499,9 @@ impl Default for LocalStorageConfig {
             auto_attach_enabled: None,
             start_in_tray: None,
             game_path: None,
+            max_sample_rate: None,
+            offset_sources: None,
+            offline_mode: None,
         }
     }
 }
@@ -174,6 +514,9 @@ pub struct GlobalState {
     pub connection_url: Option<String>,
     pub external_connection_code: Option<String>,
     pub active_offset_name: Option<String>,
+    pub map_name: Option<String>,
+    /// 현재 실제 샘플링 Hz (수집 중이 아니면 None)
+    pub sample_rate: Option<u32>,
 }
 
 impl Default for GlobalState {
@@ -184,6 +527,8 @@ impl Default for GlobalState {
             connection_url: None,
             external_connection_code: None,
             active_offset_name: None,
+            map_name: None,
+            sample_rate: None,
         }
     }
 }
@@ -1,4 +1,5 @@
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use tauri::{AppHandle, Manager};
@@ -50,3 +51,16 @@ pub fn set_global_state(app_handle: &AppHandle, value: GlobalState) {
 pub fn mutate_global_state(app_handle: &AppHandle, f: impl FnOnce(&mut GlobalState)) {
     app_handle.state::<TauriState>().global_state.send_modify(f);
 }
+
+/// 임시 파일에 쓴 뒤 교체해, 쓰는 도중 종료되어도 파일이 반쯤 쓰인 채로 남지 않게 한다.
+/// (분석 캐시, 오프셋 기록, variant 통계가 함께 쓴다)
+pub fn write_atomic(path: &Path, data: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("폴더 생성 실패")?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    fs::write(&tmp, data).context("임시 파일 쓰기 실패")?;
+    fs::rename(&tmp, path).context("파일 교체 실패")
+}
@@ -0,0 +1,204 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::analysis_cache::BuildKey;
+use crate::util::write_atomic;
+
+const CACHE_FILE: &str = "variant_stats_cache.json";
+/// 보관할 빌드 수. 오래된 빌드의 기록부터 지운다.
+const MAX_BUILDS: usize = 8;
+
+#[derive(Default, Serialize, Deserialize)]
+struct VariantStatsCache {
+    /// 최근 사용순
+    entries: Vec<BuildStats>,
+}
+
+/// 캐시 키는 분석 캐시와 같은 실행 파일 + build_id
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct BuildStats {
+    #[serde(flatten)]
+    key: BuildKey,
+    /// 마지막으로 성공한 variant 이름
+    last_success: Option<String>,
+    variants: Vec<VariantCount>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct VariantCount {
+    name: String,
+    successes: u32,
+    failures: u32,
+}
+
+/// 게임 빌드별 variant 성공/실패 기록. select_player_info가 시도 순서를 정하는 데 쓴다.
+/// 빌드를 알 수 없으면 메모리에만 기록한다.
+pub struct VariantStats {
+    path: Option<PathBuf>,
+    stats: BuildStats,
+    dirty: bool,
+}
+
+impl VariantStats {
+    pub fn load(cache_dir: &Path, key: Option<BuildKey>) -> Self {
+        let Some(key) = key else {
+            return Self::in_memory();
+        };
+        let path = cache_dir.join(CACHE_FILE);
+        let stats = load_cache(&path)
+            .entries
+            .into_iter()
+            .find(|e| e.key == key)
+            .unwrap_or_else(|| BuildStats::new(key));
+        if let Some(name) = &stats.last_success {
+            log::info!("빌드 {}에서 마지막으로 성공한 variant: {}", stats.key.build_id, name);
+        }
+        Self { path: Some(path), stats, dirty: false }
+    }
+
+    pub fn in_memory() -> Self {
+        let key = BuildKey { exe_path: String::new(), build_id: String::new() };
+        Self { path: None, stats: BuildStats::new(key), dirty: false }
+    }
+
+    /// 작을수록 먼저 시도한다: 마지막 성공 → 성공한 적 있음 → 미시도 → 실패만 함
+    pub fn rank(&self, name: &str) -> u8 {
+        if self.stats.last_success.as_deref() == Some(name) {
+            return 0;
+        }
+        match self.stats.variants.iter().find(|v| v.name == name) {
+            Some(v) if v.successes > 0 => 1,
+            None => 2,
+            Some(v) if v.failures == 0 => 2,
+            Some(_) => 3,
+        }
+    }
+
+    /// 마지막 성공 variant가 바뀌면 바로 저장하고, 나머지 카운트는 Drop 때 저장한다.
+    pub fn record(&mut self, name: &str, success: bool) {
+        let count = match self.stats.variants.iter_mut().find(|v| v.name == name) {
+            Some(count) => count,
+            None => {
+                self.stats.variants.push(VariantCount { name: name.to_string(), successes: 0, failures: 0 });
+                self.stats.variants.last_mut().unwrap()
+            }
+        };
+        if success {
+            count.successes = count.successes.saturating_add(1);
+        } else {
+            count.failures = count.failures.saturating_add(1);
+        }
+        self.dirty = true;
+
+        if success && self.stats.last_success.as_deref() != Some(name) {
+            self.stats.last_success = Some(name.to_string());
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if !self.dirty {
+            return;
+        }
+        let mut cache = load_cache(path);
+        cache.entries.retain(|e| e.key != self.stats.key);
+        cache.entries.insert(0, self.stats.clone());
+        cache.entries.truncate(MAX_BUILDS);
+        if let Err(e) = save_cache(path, &cache) {
+            log::warn!("variant 통계 저장 실패: {:#}", e);
+        }
+        self.dirty = false;
+    }
+}
+
+impl BuildStats {
+    fn new(key: BuildKey) -> Self {
+        Self { key, last_success: None, variants: Vec::new() }
+    }
+}
+
+impl Drop for VariantStats {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+fn load_cache(path: &Path) -> VariantStatsCache {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_cache(path: &Path, cache: &VariantStatsCache) -> Result<()> {
+    let data = serde_json::to_vec(cache).context("variant 통계 직렬화 실패")?;
+    write_atomic(path, &data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(build_id: &str) -> BuildKey {
+        BuildKey { exe_path: "client-win64-shipping.exe".to_string(), build_id: build_id.to_string() }
+    }
+
+    #[test]
+    fn ranks_last_success_then_any_success_then_untried_then_failures() {
+        let mut stats = VariantStats::in_memory();
+        stats.record("earlier", true);
+        stats.record("flaky", false);
+        stats.record("flaky", true);
+        stats.record("broken", false);
+        stats.record("latest", true);
+
+        assert_eq!(stats.rank("latest"), 0);
+        assert_eq!(stats.rank("earlier"), 1);
+        assert_eq!(stats.rank("flaky"), 1);
+        assert_eq!(stats.rank("untried"), 2);
+        assert_eq!(stats.rank("broken"), 3);
+
+        // 실패가 이어져도 성공한 적이 있으면 실패만 한 variant보다 앞선다.
+        stats.record("earlier", false);
+        stats.record("earlier", false);
+        assert!(stats.rank("earlier") < stats.rank("broken"));
+    }
+
+    #[test]
+    fn reloads_stats_for_the_same_build_only() {
+        let dir = std::env::temp_dir().join(format!("wuma-tracker-variant-stats-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        {
+            let mut stats = VariantStats::load(&dir, Some(key("pe:66500000-A000000")));
+            stats.record("current", true);
+            stats.record("previous", false);
+        }
+        {
+            let mut stats = VariantStats::load(&dir, Some(key("pe:66600000-A000000")));
+            stats.record("next", true);
+        }
+
+        // 실패 카운트는 Drop 때 저장된다.
+        let stats = VariantStats::load(&dir, Some(key("pe:66500000-A000000")));
+        assert_eq!(stats.rank("current"), 0);
+        assert_eq!(stats.rank("previous"), 3);
+        assert_eq!(stats.rank("next"), 2);
+
+        let other = VariantStats::load(&dir, Some(key("pe:66600000-A000000")));
+        assert_eq!(other.rank("next"), 0);
+        assert_eq!(other.rank("current"), 2);
+
+        // 빌드를 모르면 저장된 기록을 쓰지 않는다.
+        assert_eq!(VariantStats::load(&dir, None).rank("current"), 2);
+
+        drop((stats, other));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
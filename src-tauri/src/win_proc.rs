@@ -1,12 +1,13 @@
-use std::path::{Path, PathBuf};
-use std::{ffi::CStr, fs, mem, ptr::null_mut};
+use std::path::PathBuf;
+use std::{ffi::CStr, mem, ptr::null_mut};
 
+use crate::analysis_cache::BuildKey;
+use crate::game_version::GameBuild;
 use crate::offsets::{GWorldScanConfig, WuwaOffset};
-use crate::process_backend::ProcessBackend;
-use crate::types::NativeError;
-use crate::types::NativeError::{PointerChainError, ValueReadError};
+use crate::pe_scan;
+use crate::process_backend::{ProcessBackend, classify_ptr};
+use crate::types::{MemoryRegion, NativeError, PageInfo, PageState, Protection, ReadFailure, ReadFault};
 use anyhow::{Context, Result, bail};
-use serde::{Deserialize, Serialize};
 use winapi::um::minwinbase::STILL_ACTIVE;
 use winapi::um::processthreadsapi::GetExitCodeProcess;
 use winapi::{
@@ -16,7 +17,7 @@ use winapi::{
         handleapi::CloseHandle,
         memoryapi::ReadProcessMemory,
         processthreadsapi::OpenProcess,
-        psapi::{EnumProcessModulesEx, GetModuleFileNameExW, LIST_MODULES_DEFAULT},
+        psapi::{EnumProcessModulesEx, GetMappedFileNameW, GetModuleFileNameExW, LIST_MODULES_DEFAULT},
         tlhelp32::{
             CreateToolhelp32Snapshot, PROCESSENTRY32, Process32First, Process32Next,
             TH32CS_SNAPPROCESS,
@@ -26,59 +27,31 @@ use winapi::{
 };
 use winapi::um::memoryapi::VirtualQueryEx;
 use winapi::um::winnt::{
-    MEMORY_BASIC_INFORMATION, MEM_COMMIT, MEM_FREE, MEM_RESERVE, PAGE_GUARD, PAGE_NOACCESS,
+    MEMORY_BASIC_INFORMATION, MEM_COMMIT, MEM_FREE, MEM_IMAGE, MEM_MAPPED, MEM_RESERVE, PAGE_EXECUTE,
+    PAGE_EXECUTE_READ, PAGE_EXECUTE_READWRITE, PAGE_EXECUTE_WRITECOPY, PAGE_GUARD, PAGE_READONLY, PAGE_READWRITE,
+    PAGE_WRITECOPY,
 };
 
-// .pdata 스캔 배치 파라미터
-const BATCH_GAP: u64 = 4096;
-const MAX_BATCH_SIZE: usize = 256 * 1024;
-
-const CACHE_FILE: &str = "win_gworld_scan_cache.json";
-
-// ── 패턴 파싱 ─────────────────────────────────────────────────────────────────
-
-/// "48 8B 1D" → [0x48, 0x8B, 0x1D]
-fn parse_prefix(s: &str) -> Vec<u8> {
-    s.split_whitespace()
-        .filter_map(|b| u8::from_str_radix(b, 16).ok())
-        .collect()
-}
-
-/// "48 85 DB 74 ?? 41 B0 01" → [Some(0x48), Some(0x85), Some(0xDB), None, Some(0x41), ...]
-fn parse_suffix(s: &str) -> Vec<Option<u8>> {
-    s.split_whitespace()
-        .map(|b| if b == "??" { None } else { u8::from_str_radix(b, 16).ok() })
-        .collect()
-}
-
-// ── 캐시 구조체 ───────────────────────────────────────────────────────────────
-
-#[derive(Serialize, Deserialize, Default)]
-struct GworldScanCache {
-    entries: Vec<GworldScanCacheEntry>,
-}
-
-#[derive(Serialize, Deserialize, Clone)]
-struct GworldScanCacheEntry {
-    exe_path: String,
-    pe_timestamp: u32,
-    size_of_image: u32,
-    gworld_rva: u64,
-}
-
 // ── WinProc ───────────────────────────────────────────────────────────────────
 
 pub struct WinProc {
     pid: u32,
     pub base_addr: u64,
     handle: HANDLE,
+    exe_path: String,
     gworld_rva: u64,
     cache_dir: PathBuf,
     scan_config: GWorldScanConfig,
+    game_build: Option<GameBuild>,
 }
 
 impl WinProc {
-    pub fn new(name: &str, cache_dir: PathBuf, scan_config: Option<GWorldScanConfig>) -> Result<Self> {
+    pub fn new(
+        name: &str,
+        cache_dir: PathBuf,
+        scan_config: Option<GWorldScanConfig>,
+        variants: &[WuwaOffset],
+    ) -> Result<Self> {
         let scan_config = scan_config.unwrap_or_default();
         unsafe {
             let pid = Self::find_pid_by_name(name)
@@ -111,28 +84,21 @@ impl WinProc {
 
             let base_addr = h_mod as u64;
 
-            let gworld_rva = if scan_config.enabled {
-                let prefix = parse_prefix(&scan_config.prefix);
-                let suffix = parse_suffix(&scan_config.suffix);
-                match find_gworld_rva_with_cache(handle, base_addr, &cache_dir, &prefix, &suffix) {
-                    Ok(rva) => rva,
-                    Err(e) => {
-                        log::warn!("GWorld 스캔 실패, 오프셋 폴백 사용: {}", e);
-                        0
-                    }
-                }
-            } else {
-                log::info!("GWorld 자동 탐색 비활성화됨 (원격 설정), 오프셋 폴백 사용");
-                0
-            };
+            let exe_path = get_module_path(handle).unwrap_or_default();
+            let mut proc = WinProc { pid, base_addr, handle, exe_path, gworld_rva: 0, cache_dir, scan_config, game_build: None };
+            proc.game_build = pe_scan::game_build(&proc, base_addr, &proc.exe_path);
+            proc.gworld_rva = pe_scan::initial_gworld_rva(
+                &proc, base_addr, &proc.exe_path, &proc.cache_dir, &proc.scan_config, variants,
+            );
 
             log::info!(
-                "Process '{}' connected! PID: {}, Base: {:X}, GWorld RVA: {}",
+                "Process '{}' connected! PID: {}, Base: {:X}, Build: {}, GWorld RVA: {}",
                 name, pid, base_addr,
-                if gworld_rva != 0 { format!("{:X}", gworld_rva) } else { "폴백".to_string() }
+                proc.game_build.as_ref().map(|b| b.to_string()).unwrap_or_else(|| "?".to_string()),
+                if proc.gworld_rva != 0 { format!("{:X}", proc.gworld_rva) } else { "폴백".to_string() }
             );
 
-            Ok(WinProc { pid, base_addr, handle, gworld_rva, cache_dir, scan_config })
+            Ok(proc)
         }
     }
 
@@ -141,34 +107,28 @@ impl WinProc {
         pid: u32,
         cache_dir: PathBuf,
         scan_config: Option<GWorldScanConfig>,
+        variants: &[WuwaOffset],
     ) -> Result<Self> {
         let scan_config = scan_config.unwrap_or_default();
 
         let base_addr = Self::wait_for_base_addr(handle)?;
 
-        let gworld_rva = if scan_config.enabled {
-            let prefix = parse_prefix(&scan_config.prefix);
-            let suffix = parse_suffix(&scan_config.suffix);
-            match find_gworld_rva_with_cache(handle, base_addr, &cache_dir, &prefix, &suffix) {
-                Ok(rva) => rva,
-                Err(e) => {
-                    log::warn!("GWorld 스캔 실패, 오프셋 폴백 사용: {}", e);
-                    0
-                }
-            }
-        } else {
-            log::info!("GWorld 자동 탐색 비활성화됨 (원격 설정), 오프셋 폴백 사용");
-            0
-        };
+        let exe_path = get_module_path(handle).unwrap_or_default();
+        let mut proc = WinProc { pid, base_addr, handle, exe_path, gworld_rva: 0, cache_dir, scan_config, game_build: None };
+        proc.game_build = pe_scan::game_build(&proc, base_addr, &proc.exe_path);
+        proc.gworld_rva = pe_scan::initial_gworld_rva(
+            &proc, base_addr, &proc.exe_path, &proc.cache_dir, &proc.scan_config, variants,
+        );
 
         log::info!(
-            "프로세스 직접 실행 연결됨! PID: {}, Base: {:X}, GWorld RVA: {}",
+            "프로세스 직접 실행 연결됨! PID: {}, Base: {:X}, Build: {}, GWorld RVA: {}",
             pid,
             base_addr,
-            if gworld_rva != 0 { format!("{:X}", gworld_rva) } else { "폴백".to_string() }
+            proc.game_build.as_ref().map(|b| b.to_string()).unwrap_or_else(|| "?".to_string()),
+            if proc.gworld_rva != 0 { format!("{:X}", proc.gworld_rva) } else { "폴백".to_string() }
         );
 
-        Ok(WinProc { pid, base_addr, handle, gworld_rva, cache_dir, scan_config })
+        Ok(proc)
     }
 
     fn wait_for_base_addr(handle: HANDLE) -> Result<u64> {
@@ -253,16 +213,15 @@ impl ProcessBackend for WinProc {
                 Ok(())
             } else {
                 let code = std::io::Error::last_os_error().raw_os_error().unwrap_or(-1);
-                let hint = match code {
-                    299 => "[PARTIAL]",
-                    5   => "[ACCESS]",
-                    6   => "[INV_HDL]",
-                    _   => "",
+                let fault = match code {
+                    299 => ReadFault::Partial,
+                    5   => ReadFault::AccessDenied,
+                    6   => ReadFault::InvalidHandle,
+                    _   => ReadFault::Other,
                 };
-                let page = describe_page(self.handle, address);
-                Err(ValueReadError {
-                    message: format!("RPM@{:X} e{}{} {}", address, code, hint, page),
-                })
+                let mut failure = ReadFailure::at(address).fault(fault, Some(code));
+                failure.page = self.page_info(address);
+                Err(NativeError::value_read(failure))
             }
         }
     }
@@ -271,6 +230,35 @@ impl ProcessBackend for WinProc {
         self.gworld_rva != 0
     }
 
+    fn module_base(&self) -> u64 {
+        self.base_addr
+    }
+
+    fn gworld_rva(&self) -> Option<u64> {
+        (self.gworld_rva != 0).then_some(self.gworld_rva)
+    }
+
+    fn game_build(&self) -> Option<&GameBuild> {
+        self.game_build.as_ref()
+    }
+
+    fn build_key(&self) -> Option<BuildKey> {
+        self.game_build.as_ref().map(|build| pe_scan::game_build_key(&self.exe_path, build))
+    }
+
+    fn regions(&self) -> Vec<MemoryRegion> {
+        enumerate_regions(self.handle)
+    }
+
+    fn query_region(&self, address: u64) -> Option<MemoryRegion> {
+        query_region(self.handle, address)
+    }
+
+    // 읽기 실패마다 불리므로 VirtualQueryEx 한 번만 한다. (모듈 이름은 진단 리포트에서 query_region으로)
+    fn page_info(&self, address: u64) -> Option<PageInfo> {
+        virtual_query(self.handle, address).map(|mbi| region_from_mbi(&mbi, None).page_info())
+    }
+
     fn active_offset_name(&self, offset: &WuwaOffset) -> String {
         if self.gworld_rva != 0 {
             format!("{:X}", self.gworld_rva)
@@ -279,32 +267,25 @@ impl ProcessBackend for WinProc {
         }
     }
 
-    fn rescan_gworld(&mut self) {
+    fn set_scan_config(&mut self, scan_config: GWorldScanConfig) -> bool {
+        if self.scan_config == scan_config {
+            return false;
+        }
+        self.scan_config = scan_config;
+        true
+    }
+
+    fn rescan_gworld(&mut self, variants: &[WuwaOffset]) {
         if !self.scan_config.enabled {
             log::info!("GWorld 재스캔 스킵 (원격 설정으로 비활성화됨)");
             return;
         }
-        let prefix = parse_prefix(&self.scan_config.prefix);
-        let suffix = parse_suffix(&self.scan_config.suffix);
-        match scan_gworld_rva(self.handle, self.base_addr, &prefix, &suffix) {
+        match pe_scan::rescan_gworld_rva(
+            self, self.base_addr, &self.exe_path, &self.cache_dir, &self.scan_config, variants,
+        ) {
             Ok(rva) => {
                 log::info!("GWorld 재스캔 성공: RVA 0x{:X}", rva);
                 self.gworld_rva = rva;
-                if let Ok((timestamp, size_of_image, _, _)) =
-                    read_pe_exception_dir(self.handle, self.base_addr)
-                {
-                    let exe_path = get_module_path(self.handle).unwrap_or_default();
-                    let cache_path = self.cache_dir.join(CACHE_FILE);
-                    let mut cache = load_cache(&cache_path);
-                    cache.entries.retain(|e| e.exe_path != exe_path);
-                    cache.entries.push(GworldScanCacheEntry {
-                        exe_path,
-                        pe_timestamp: timestamp,
-                        size_of_image,
-                        gworld_rva: rva,
-                    });
-                    save_cache(&cache_path, &cache);
-                }
             }
             Err(e) => {
                 log::warn!("GWorld 재스캔 실패, 폴백 유지: {}", e);
@@ -320,8 +301,8 @@ impl ProcessBackend for WinProc {
             (offset.global_gworld, "fb")
         };
         let target = self.base_addr + rva;
-        self.read_memory::<u64>(target).map_err(|e| PointerChainError {
-            message: format!("gworld@{:X} rva={:X}[{}]: {}", target, rva, source, e),
+        self.read_memory::<u64>(target).map_err(|e| {
+            e.in_chain(&format!("gworld[{}]", source), self.base_addr, rva, classify_ptr(self.base_addr))
         })
     }
 }
@@ -339,9 +320,9 @@ impl Drop for WinProc {
 
 unsafe impl Send for WinProc {}
 
-// ── PE 헤더 파싱 ──────────────────────────────────────────────────────────────
+// ── 진단 / 모듈 정보 ──────────────────────────────────────────────────────────
 
-fn describe_page(handle: HANDLE, address: u64) -> String {
+fn virtual_query(handle: HANDLE, address: u64) -> Option<MEMORY_BASIC_INFORMATION> {
     unsafe {
         let mut mbi: MEMORY_BASIC_INFORMATION = mem::zeroed();
         let r = VirtualQueryEx(
@@ -350,55 +331,90 @@ fn describe_page(handle: HANDLE, address: u64) -> String {
             &mut mbi,
             mem::size_of::<MEMORY_BASIC_INFORMATION>(),
         );
-        if r == 0 {
-            return format!("[vqex_e{}]", std::io::Error::last_os_error().raw_os_error().unwrap_or(-1));
-        }
-        let state = match mbi.State {
-            MEM_COMMIT => "CMT",
-            MEM_RESERVE => "RSV",
-            MEM_FREE => "FREE",
-            _ => "?",
-        };
-        let mut prot = String::new();
-        if mbi.Protect & PAGE_GUARD != 0 { prot.push_str("+GUARD"); }
-        if mbi.Protect & PAGE_NOACCESS != 0 { prot.push_str("+NAX"); }
-        let verdict = match mbi.State {
-            MEM_FREE | MEM_RESERVE => "!commit",
-            MEM_COMMIT if mbi.Protect & (PAGE_GUARD | PAGE_NOACCESS) != 0 => "!ACE",
-            MEM_COMMIT => "!tmp",
-            _ => "",
-        };
-        format!("[{}{}{} @{:X}+{:X}]", state, prot, verdict, mbi.BaseAddress as u64, mbi.RegionSize)
+        (r != 0).then_some(mbi)
     }
 }
 
-fn rpm_u32(handle: HANDLE, addr: u64) -> Option<u32> {
-    let mut buf = [0u8; 4];
-    let mut br = 0usize;
-    let ok = unsafe {
-        ReadProcessMemory(handle, addr as _, buf.as_mut_ptr() as _, 4, &mut br) != 0 && br == 4
+fn query_region(handle: HANDLE, address: u64) -> Option<MemoryRegion> {
+    let Some(mbi) = virtual_query(handle, address) else {
+        log::debug!(
+            "VirtualQueryEx@{:X} 실패: e{}",
+            address,
+            std::io::Error::last_os_error().raw_os_error().unwrap_or(-1)
+        );
+        return None;
     };
-    ok.then(|| u32::from_le_bytes(buf))
+    let module = mapped_file_name(handle, &mbi);
+    Some(region_from_mbi(&mbi, module))
 }
 
-fn rpm_buf(handle: HANDLE, addr: u64, buf: &mut [u8]) -> bool {
-    let mut br = 0usize;
-    unsafe {
-        ReadProcessMemory(handle, addr as _, buf.as_mut_ptr() as _, buf.len(), &mut br) != 0
-            && br == buf.len()
+/// 유저 모드 주소 공간 전체를 VirtualQueryEx로 훑는다. (Free 영역 제외)
+/// 같은 할당(AllocationBase)에 속한 연속 영역은 매핑 파일 이름을 한 번만 조회한다.
+fn enumerate_regions(handle: HANDLE) -> Vec<MemoryRegion> {
+    let mut regions = Vec::new();
+    let mut module_cache: Option<(u64, Option<String>)> = None;
+    let mut address = 0u64;
+    while let Some(mbi) = virtual_query(handle, address) {
+        if mbi.State != MEM_FREE {
+            let allocation_base = mbi.AllocationBase as u64;
+            let module = match &module_cache {
+                Some((base, module)) if *base == allocation_base => module.clone(),
+                _ => {
+                    let module = mapped_file_name(handle, &mbi);
+                    module_cache = Some((allocation_base, module.clone()));
+                    module
+                }
+            };
+            regions.push(region_from_mbi(&mbi, module));
+        }
+        let next = (mbi.BaseAddress as u64).saturating_add(mbi.RegionSize as u64);
+        if next <= address {
+            break;
+        }
+        address = next;
     }
+    regions
 }
 
-fn read_pe_exception_dir(handle: HANDLE, base: u64) -> Result<(u32, u32, u32, u32)> {
-    let e_lfanew = rpm_u32(handle, base + 0x3C).context("e_lfanew 읽기 실패")? as u64;
-    let pe = base + e_lfanew;
-    let timestamp = rpm_u32(handle, pe + 8).context("TimeDateStamp 읽기 실패")?;
-    let opt = pe + 24;
-    let size_of_image = rpm_u32(handle, opt + 56).context("SizeOfImage 읽기 실패")?;
-    let dd = opt + 112 + 24;
-    let pdata_rva = rpm_u32(handle, dd).context(".pdata RVA 읽기 실패")?;
-    let pdata_size = rpm_u32(handle, dd + 4).context(".pdata size 읽기 실패")?;
-    Ok((timestamp, size_of_image, pdata_rva, pdata_size))
+fn region_from_mbi(mbi: &MEMORY_BASIC_INFORMATION, module: Option<String>) -> MemoryRegion {
+    let state = match mbi.State {
+        MEM_COMMIT => PageState::Commit,
+        MEM_RESERVE => PageState::Reserve,
+        MEM_FREE => PageState::Free,
+        _ => PageState::Unknown,
+    };
+    // 하위 바이트가 기본 보호 속성, PAGE_GUARD 등은 수식 비트
+    let base = mbi.Protect & 0xFF;
+    let readable = PAGE_READONLY
+        | PAGE_READWRITE
+        | PAGE_WRITECOPY
+        | PAGE_EXECUTE_READ
+        | PAGE_EXECUTE_READWRITE
+        | PAGE_EXECUTE_WRITECOPY;
+    let writable = PAGE_READWRITE | PAGE_WRITECOPY | PAGE_EXECUTE_READWRITE | PAGE_EXECUTE_WRITECOPY;
+    let executable = PAGE_EXECUTE | PAGE_EXECUTE_READ | PAGE_EXECUTE_READWRITE | PAGE_EXECUTE_WRITECOPY;
+    MemoryRegion {
+        base: mbi.BaseAddress as u64,
+        size: mbi.RegionSize as u64,
+        state,
+        protection: Protection {
+            read: base & readable != 0,
+            write: base & writable != 0,
+            execute: base & executable != 0,
+            guard: mbi.Protect & PAGE_GUARD != 0,
+        },
+        module,
+    }
+}
+
+/// 이미지/파일 매핑 영역의 파일 경로. (\Device\HarddiskVolumeN\... 형식의 NT 경로)
+fn mapped_file_name(handle: HANDLE, mbi: &MEMORY_BASIC_INFORMATION) -> Option<String> {
+    if mbi.State == MEM_FREE || mbi.Type & (MEM_IMAGE | MEM_MAPPED) == 0 {
+        return None;
+    }
+    let mut buf = vec![0u16; 1024];
+    let len = unsafe { GetMappedFileNameW(handle, mbi.BaseAddress, buf.as_mut_ptr(), buf.len() as u32) };
+    (len != 0).then(|| String::from_utf16_lossy(&buf[..len as usize]))
 }
 
 fn get_module_path(handle: HANDLE) -> Result<String> {
@@ -411,146 +427,3 @@ fn get_module_path(handle: HANDLE) -> Result<String> {
     }
     Ok(String::from_utf16_lossy(&buf[..len as usize]))
 }
-
-// ── .pdata 기반 GWorld RVA 스캔 ───────────────────────────────────────────────
-
-fn scan_gworld_rva(handle: HANDLE, base: u64, prefix: &[u8], suffix: &[Option<u8>]) -> Result<u64> {
-    let (_, size_of_image, pdata_rva, pdata_size) = read_pe_exception_dir(handle, base)?;
-
-    if pdata_rva == 0 || pdata_size < 12 {
-        bail!(".pdata 섹션을 찾지 못했습니다.");
-    }
-
-    let entry_count = pdata_size as usize / 12;
-    let mut pdata = vec![0u8; entry_count * 12];
-    if !rpm_buf(handle, base + pdata_rva as u64, &mut pdata) {
-        bail!(".pdata 읽기 실패");
-    }
-
-    log::info!("GWorld 스캔: .pdata 함수 {} 개", entry_count);
-
-    let instr_len = prefix.len() + 4; // prefix + disp32
-    let pattern_total = instr_len + suffix.len();
-
-    let mut funcs: Vec<(u32, u32)> = (0..entry_count)
-        .map(|i| {
-            let b = u32::from_le_bytes(pdata[i * 12..i * 12 + 4].try_into().unwrap());
-            let e = u32::from_le_bytes(pdata[i * 12 + 4..i * 12 + 8].try_into().unwrap());
-            (b, e)
-        })
-        .filter(|&(b, e)| b > 0 && e > b && e <= size_of_image)
-        .collect();
-    funcs.sort_unstable_by_key(|&(b, _)| b);
-
-    let mut i = 0;
-    while i < funcs.len() {
-        let batch_start = funcs[i].0 as u64;
-        let mut batch_end = funcs[i].1 as u64;
-        let mut j = i + 1;
-
-        while j < funcs.len() {
-            let gap = funcs[j].0 as u64 - batch_end;
-            let new_end = funcs[j].1 as u64;
-            if gap > BATCH_GAP || new_end - batch_start > MAX_BATCH_SIZE as u64 {
-                break;
-            }
-            batch_end = new_end;
-            j += 1;
-        }
-
-        let read_size = (batch_end - batch_start) as usize;
-        let mut buf = vec![0u8; read_size];
-
-        if rpm_buf(handle, base + batch_start, &mut buf) {
-            let search_end = buf.len().saturating_sub(pattern_total - 1);
-            'search: for off in 0..search_end {
-                // prefix 매칭
-                if buf[off..off + prefix.len()] != *prefix {
-                    continue;
-                }
-                // suffix 매칭 (wildcard 지원)
-                let suf_start = off + instr_len;
-                for (k, &byte) in suffix.iter().enumerate() {
-                    if let Some(b) = byte {
-                        if buf[suf_start + k] != b {
-                            continue 'search;
-                        }
-                    }
-                }
-
-                let disp = i32::from_le_bytes(
-                    buf[off + prefix.len()..off + prefix.len() + 4].try_into().unwrap()
-                );
-                let instr_rva = batch_start + off as u64;
-                let gworld_rva = ((instr_rva as i64) + instr_len as i64 + disp as i64) as u64;
-
-                if gworld_rva > 0 && gworld_rva < size_of_image as u64 {
-                    log::info!(
-                        "GWorld RVA 발견: 0x{:X} (명령어 RVA 0x{:X}, disp {:+#X})",
-                        gworld_rva, instr_rva, disp
-                    );
-                    return Ok(gworld_rva);
-                }
-            }
-        }
-
-        i = j;
-    }
-
-    bail!("GWorld 패턴을 찾지 못했습니다.")
-}
-
-// ── 캐시 ─────────────────────────────────────────────────────────────────────
-
-fn load_cache(path: &Path) -> GworldScanCache {
-    fs::read_to_string(path)
-        .ok()
-        .and_then(|s| serde_json::from_str(&s).ok())
-        .unwrap_or_default()
-}
-
-fn save_cache(path: &Path, cache: &GworldScanCache) {
-    if let Some(parent) = path.parent() {
-        let _ = fs::create_dir_all(parent);
-    }
-    if let Ok(data) = serde_json::to_string(cache) {
-        let _ = fs::write(path, data);
-    }
-}
-
-fn find_gworld_rva_with_cache(
-    handle: HANDLE,
-    base: u64,
-    cache_dir: &Path,
-    prefix: &[u8],
-    suffix: &[Option<u8>],
-) -> Result<u64> {
-    let exe_path = get_module_path(handle).unwrap_or_default();
-    let (timestamp, size_of_image, _, _) = read_pe_exception_dir(handle, base)?;
-
-    let cache_path = cache_dir.join(CACHE_FILE);
-    let mut cache = load_cache(&cache_path);
-
-    if let Some(entry) = cache.entries.iter().find(|e| {
-        e.exe_path == exe_path
-            && e.pe_timestamp == timestamp
-            && e.size_of_image == size_of_image
-    }) {
-        log::info!("캐시된 GWorld RVA 사용: 0x{:X}", entry.gworld_rva);
-        return Ok(entry.gworld_rva);
-    }
-
-    log::info!("GWorld RVA 캐시 미스 → .pdata 스캔 시작");
-    let gworld_rva = scan_gworld_rva(handle, base, prefix, suffix)?;
-
-    cache.entries.retain(|e| e.exe_path != exe_path);
-    cache.entries.push(GworldScanCacheEntry {
-        exe_path,
-        pe_timestamp: timestamp,
-        size_of_image,
-        gworld_rva,
-    });
-    save_cache(&cache_path, &cache);
-
-    Ok(gworld_rva)
-}
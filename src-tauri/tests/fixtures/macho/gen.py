@@ -0,0 +1,49 @@
+# macho_scan 테스트용 최소 Mach-O 생성기: python3 gen.py
+#   arm64.bin : ADRP+LDR x8 → 0x100004A08 (3회), __DATA 미끼 1회, 레지스터 불일치 1회, __TEXT 대상 1회,
+#               ADRP+ADD x10 → 0x100005010
+#   x86_64.bin: mov rbx, [rip+disp] → 0x100004C10 (2회)
+#   fat.bin   : x86_64 + arm64
+import struct
+def seg(name, vmaddr, vmsize, fileoff, filesize, prot, sects):
+    b = struct.pack('<II16sQQQQiiII', 0x19, 72+80*len(sects), name.encode(), vmaddr, vmsize, fileoff, filesize, 7, prot, len(sects), 0)
+    for (sn, addr, size, off) in sects:
+        b += struct.pack('<16s16sQQIIIIIIII', sn.encode(), name.encode(), addr, size, off, 2, 0, 0, 0x80000400, 0, 0, 0)
+    return b
+def macho(cputype, code):
+    text = seg('__TEXT', 0x100000000, 0x4000, 0, 0x4000, 5, [('__text', 0x100001000, len(code), 0x1000)])
+    data = seg('__DATA', 0x100004000, 0x4000, 0x4000, 0x1000, 3, [('__data', 0x100004000, 0x1000, 0x4000)])
+    cmds = text + data
+    hdr = struct.pack('<IiiIIIII', 0xFEEDFACF, cputype, 3 if cputype==0x01000007 else 0, 2, 2, len(cmds), 0, 0)
+    b = bytearray(0x5000); b[:len(hdr)+len(cmds)] = hdr+cmds; b[0x1000:0x1000+len(code)] = code
+    return bytes(b)
+def adrp(rd, pc, target):
+    imm = ((target & ~0xFFF) - (pc & ~0xFFF)) >> 12
+    return 0x90000000 | ((imm & 3) << 29) | (((imm >> 2) & 0x7FFFF) << 5) | rd
+def ldr(rt, rn, off): return 0xF9400000 | ((off//8) << 10) | (rn << 5) | rt
+def add(rd, rn, imm): return 0x91000000 | (imm << 10) | (rn << 5) | rd
+CBZ = 0xB4000108  # cbz x8, +32
+def arm_code():
+    base = 0x100001000; out = []
+    def emit(*ins): out.extend(ins)
+    GW = 0x100004A08
+    for i in range(3):
+        pc = base + len(out)*4; emit(adrp(8, pc, GW), ldr(8, 8, GW & 0xFFF), CBZ, 0xD503201F)
+    pc = base + len(out)*4; emit(adrp(8, pc, 0x100004100), ldr(8, 8, 0x100), CBZ, 0xD503201F)  # decoy once
+    pc = base + len(out)*4; emit(adrp(9, pc, 0x100004200), ldr(8, 8, 0x200), CBZ, 0xD503201F)  # reg mismatch
+    pc = base + len(out)*4; emit(adrp(8, pc, 0x100000800), ldr(8, 8, 0x800), CBZ, 0xD503201F)  # __TEXT target
+    pc = base + len(out)*4; emit(adrp(10, pc, 0x100005000), add(10, 10, 0x10), 0xD503201F)
+    return b''.join(struct.pack('<I', x) for x in out)
+def x86_code():
+    code = bytearray(b'\x90'*0x40)
+    for at in (0x10, 0x30):
+        instr = 0x100001000 + at
+        disp = 0x100004C10 - (instr + 7)
+        code[at:at+15] = b'\x48\x8B\x1D' + struct.pack('<i', disp) + b'\x48\x85\xDB\x74\x05\x41\xB0\x01'
+    return bytes(code)
+a = macho(0x0100000C, arm_code()); x = macho(0x01000007, x86_code())
+open('arm64.bin','wb').write(a); open('x86_64.bin','wb').write(x)
+fat = bytearray(struct.pack('>II', 0xCAFEBABE, 2))
+fat += struct.pack('>iiIII', 0x01000007, 3, 0x4000, len(x), 14)
+fat += struct.pack('>iiIII', 0x0100000C, 0, 0xC000, len(a), 14)
+fat += b'\0'*(0x4000-len(fat)) + x; fat += b'\0'*(0xC000-len(fat)) + a
+open('fat.bin','wb').write(fat)
@@ -5,4 +5,7 @@ export default interface AppConfig {
   autoAttachEnabled?: boolean;
   startInTray?: boolean;
   gamePath?: string;
+  maxSampleRate?: number;
+  offsetSources?: string[];
+  offlineMode?: boolean;
 }
\ No newline at end of file
@@ -3,5 +3,7 @@ export default interface GlobalState {
   serverState: number,
   connectionUrl?: string,
   externalConnectionCode?: string,
-  activeOffsetName?: string
+  activeOffsetName?: string,
+  mapName?: string,
+  sampleRate?: number
 }
\ No newline at end of file
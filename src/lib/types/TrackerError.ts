@@ -0,0 +1,33 @@
+export type TrackerErrorCode =
+  | 'process-terminated'
+  | 'offsets-loading'
+  | 'unsupported-game-version'
+  | 'access-denied'
+  | 'invalid-handle'
+  | 'world-loading'
+  | 'memory-protected'
+  | 'read-failed';
+
+export interface ReadFailure {
+  stage?: string,
+  address: string,
+  base?: string,
+  fieldOffset?: string,
+  pointer?: 'null' | 'near-null' | 'non-canonical' | 'misaligned' | 'ok',
+  fault?: 'partial' | 'access-denied' | 'invalid-handle' | 'unmapped' | 'other',
+  osCode?: number,
+  page?: {
+    state: 'commit' | 'reserve' | 'free' | 'unknown',
+    guard: boolean,
+    noAccess: boolean,
+    regionBase: string,
+    regionSize: string,
+  },
+  note?: string,
+}
+
+export default interface TrackerError {
+  code: TrackerErrorCode,
+  message: string,
+  detail?: ReadFailure,
+}